@@ -46,6 +46,7 @@ fn test_reading() {
     assert_eq!(val, bufr.trim().parse().unwrap());
 
     proc.kill().unwrap();
+    proc.wait().unwrap();
 }
 
 fn main() {
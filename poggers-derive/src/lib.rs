@@ -3,8 +3,29 @@ use proc_macro2::Span;
 use proc_macro_crate::crate_name;
 // use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
-use syn::{parse::Parse, parse_macro_input, punctuated::Punctuated, Ident, ItemFn, Token};
+use syn::{
+    parse::Parse, parse_macro_input, punctuated::Punctuated, DeriveInput, Ident, ItemFn,
+    ItemStruct, LitStr, Token,
+};
 
+mod pod;
+mod remote_struct;
+mod sig;
+mod signature_table;
+
+/// the path to the poggers crate from where the macro is used
+fn poggers_crate() -> proc_macro2::TokenStream {
+    match crate_name("poggers").expect("poggers-derive to be found") {
+        proc_macro_crate::FoundCrate::Itself => quote!(crate),
+        proc_macro_crate::FoundCrate::Name(x) => {
+            let i = Ident::new(&x, Span::call_site());
+            quote!(#i)
+        }
+    }
+}
+
+// the arguments only affect the generated DllMain
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
 struct CreateEntryArguments {
     no_console: bool,
     no_thread: bool,
@@ -45,6 +66,7 @@ impl Parse for CreateEntryArguments {
 /// On windows, this will automatically allocate a console, if you don't want to do that, use the `no_console` attribute
 /// On windows, this will automatically free the console upon dll unload, if you don't want to do that, use the `no_free` attribute
 #[proc_macro_attribute]
+#[cfg_attr(not(target_os = "windows"), allow(unused_variables))]
 pub fn create_entry(attr: TokenStream, item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as ItemFn);
     let inputb = input.clone();
@@ -53,13 +75,7 @@ pub fn create_entry(attr: TokenStream, item: TokenStream) -> TokenStream {
     let input_name = input.sig.ident;
     let has_hmd = !input.sig.inputs.is_empty();
 
-    let curr_crate = match crate_name("poggers").expect("poggers-derive to be found") {
-        proc_macro_crate::FoundCrate::Itself => quote!(crate),
-        proc_macro_crate::FoundCrate::Name(x) => {
-            let i = Ident::new(&x, Span::call_site());
-            quote!(#i)
-        }
-    };
+    let curr_crate = poggers_crate();
 
     let ret = input.sig.output;
 
@@ -167,3 +183,101 @@ pub fn create_entry(attr: TokenStream, item: TokenStream) -> TokenStream {
         #generated
     })
 }
+
+/// Implements `Pod` for a struct, so it can be read with `Mem::read_pod` and the other typed reads.
+/// ## Notes
+/// The struct has to be `#[repr(C)]` or `#[repr(transparent)]`, every field has to be `Pod` and there may not be any
+/// padding between the fields, which is all checked at compile time.
+/// ```ignore
+/// #[derive(poggers_derive::Pod, Clone, Copy)]
+/// #[repr(C)]
+/// struct Entity {
+///     id: u32,
+///     health: f32,
+///     position: [f32; 3],
+///     _pad: [u8; 4],
+///     owner: Pointer<Entity>,
+/// }
+/// ```
+#[proc_macro_derive(Pod)]
+pub fn derive_pod(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    pod::derive(input, poggers_crate())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Generates a remote view of a structure in another process, from the `#[offset(..)]` of its fields.
+/// the view of `Player` is `PlayerRemote`, created with `RemotePtr::view`, and has for every field
+/// * `health_ptr()` - a typed `RemotePtr` to the field
+/// * `health()` / `set_health(&value)` - read and write the field
+///
+/// fields marked `#[offset(0x18, nested)]` are another `RemoteStruct`, their getter returns its view.
+/// `Pointer<T>` fields are followed by their getter, returning a `RemotePtr` to the `T`.
+/// ## Notes
+/// The fields are checked to not overlap at compile time. The size of the structure can be given with
+/// `#[remote(size = 0x200)]`, which checks that every field fits.
+/// `Pointer<T>` fields have the pointer width of the compiling target, in a process with another pointer width their
+/// getter, setter and the read of the whole structure fail with `MemError::PointerWidthMismatch`.
+/// ```ignore
+/// #[derive(poggers_derive::RemoteStruct)]
+/// #[remote(size = 0x200)]
+/// struct Player {
+///     #[offset(0x10)]
+///     health: f32,
+///     #[offset(0x1A8)]
+///     target: Pointer<Player>,
+/// }
+/// ```
+#[proc_macro_derive(RemoteStruct, attributes(offset, remote))]
+pub fn derive_remote_struct(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    remote_struct::derive(input, poggers_crate())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Turns a struct of `#[sig(..)]` fields into a resolver, which fills every field with the address its signature
+/// resolves to in a module. all signatures are scanned for in a single pass over the module.
+/// ## Arguments
+/// * `rip = n` - resolve the RIP relative operand of the instruction at the match, with the displacement at offset `n`
+/// * `len = n` - the length of the instruction when `rip` is used, `rip + 4` by default
+/// * `offset = n` - added to the match before anything else, may be negative
+/// ## Notes
+/// `resolve` returns a `SignatureTableError` listing every signature which failed, instead of only the first one.
+/// ```ignore
+/// #[poggers_derive::signature_table]
+/// struct Offsets {
+///     #[sig("48 8B 05 ? ? ? ? 48 85 C0", rip = 3)]
+///     world: usize,
+///     #[sig("E8 ? ? ? ? 84 C0 74 ?", rip = 1)]
+///     is_alive: usize,
+///     #[sig("89 83 ? ? ? ? 48 8B 5C 24", offset = -5)]
+///     update_health: usize,
+/// }
+/// let offsets = Offsets::resolve(&process.get_base_module()?)?;
+/// ```
+#[proc_macro_attribute]
+pub fn signature_table(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as ItemStruct);
+    signature_table::expand(input, poggers_crate())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Parses an IDA style pattern at compile time into a `Pattern`, which can be scanned for without parsing it again.
+/// a malformed pattern is a compile error pointing at the part which could not be parsed.
+/// ## Notes
+/// Supports the same syntax as `Signature::from_ida`, byte sets are only allowed when every byte of them can be
+/// matched with a single value and mask, e.g. `[48|4C]` but not `[48|50]`.
+/// ```ignore
+/// const WORLD: poggers::sigscan::Pattern = poggers_derive::sig!("48 8B 05 ? ? ? ? [48|4C] 85 C0");
+/// let found = module.scan(WORLD)?;
+/// ```
+#[proc_macro]
+pub fn sig(item: TokenStream) -> TokenStream {
+    let pattern = parse_macro_input!(item as LitStr);
+    sig::expand(pattern, poggers_crate())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
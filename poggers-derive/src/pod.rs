@@ -0,0 +1,64 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{spanned::Spanned, Data, DeriveInput, Error};
+
+/// is the struct `#[repr(C)]` or `#[repr(transparent)]`, which give it a defined layout
+fn has_defined_layout(input: &DeriveInput) -> syn::Result<bool> {
+    let mut defined = false;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") || meta.path.is_ident("transparent") {
+                defined = true;
+            }
+            // skip the arguments of `align(n)` and `packed(n)`
+            if meta.input.peek(syn::token::Paren) {
+                let _ = meta.input.parse::<proc_macro2::Group>()?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(defined)
+}
+
+pub(crate) fn derive(input: DeriveInput, krate: TokenStream) -> syn::Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            input.ident.span(),
+            "Pod can only be derived for structs",
+        ));
+    };
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "Pod can not be derived for generic structs",
+        ));
+    }
+    if !has_defined_layout(&input)? {
+        return Err(Error::new(
+            input.ident.span(),
+            "Pod needs the struct to be #[repr(C)] or #[repr(transparent)]",
+        ));
+    }
+    let name = &input.ident;
+    let types: Vec<_> = data.fields.iter().map(|field| &field.ty).collect();
+    let padding = format!(
+        "`{name}` has padding, which would be written uninitialized, add explicit fields for it"
+    );
+    Ok(quote! {
+        // every field has to be pod
+        const _: fn() = || {
+            fn is_pod<T: #krate::structures::pod::Pod>() {}
+            #(is_pod::<#types>();)*
+        };
+        const _: () = ::std::assert!(
+            ::std::mem::size_of::<#name>() == 0 #(+ ::std::mem::size_of::<#types>())*,
+            #padding
+        );
+
+        unsafe impl #krate::structures::pod::Pod for #name {}
+    })
+}
@@ -0,0 +1,350 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    Data, DeriveInput, Error, Fields, GenericArgument, Ident, LitInt, PathArguments, Token, Type,
+};
+
+/// the arguments of `#[offset(0x10)]` or `#[offset(0x10, nested)]`
+struct OffsetArguments {
+    offset: LitInt,
+    nested: bool,
+}
+
+impl Parse for OffsetArguments {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let offset: LitInt = input.parse()?;
+        let mut nested = false;
+        if input.parse::<Option<Token![,]>>()?.is_some() {
+            let flag: Ident = input.parse()?;
+            if flag != "nested" {
+                return Err(Error::new(flag.span(), "expected `nested`"));
+            }
+            nested = true;
+        }
+        Ok(OffsetArguments { offset, nested })
+    }
+}
+
+/// the arguments of `#[remote(size = 0x200)]`
+struct RemoteArguments {
+    size: LitInt,
+}
+
+impl Parse for RemoteArguments {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Ident = input.parse()?;
+        if key != "size" {
+            return Err(Error::new(key.span(), "expected `size = <bytes>`"));
+        }
+        input.parse::<Token![=]>()?;
+        Ok(RemoteArguments {
+            size: input.parse()?,
+        })
+    }
+}
+
+/// how a field is read and written
+enum FieldKind {
+    /// a plain value
+    Value,
+    /// another remote structure, embedded in this one
+    Nested,
+    /// a `Pointer<T>`, with the type it points to
+    Pointer(Box<Type>),
+}
+
+struct RemoteField {
+    name: Ident,
+    ty: Type,
+    offset: usize,
+    offset_lit: LitInt,
+    kind: FieldKind,
+}
+
+/// the <T> of a `Pointer<T>`
+fn pointee(ty: &Type) -> Option<Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let last = path.path.segments.last()?;
+    if last.ident != "Pointer" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &last.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(ty) if args.args.len() == 1 => Some(ty.clone()),
+        _ => None,
+    }
+}
+
+fn parse_fields(input: &DeriveInput) -> syn::Result<Vec<RemoteField>> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            input.ident.span(),
+            "RemoteStruct can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new(
+            data.fields.span(),
+            "RemoteStruct needs named fields",
+        ));
+    };
+    let mut result = Vec::new();
+    for field in &fields.named {
+        let name = field.ident.clone().unwrap();
+        let attr = field
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("offset"))
+            .ok_or_else(|| {
+                Error::new(
+                    name.span(),
+                    format!("field `{name}` is missing its #[offset(..)]"),
+                )
+            })?;
+        let args: OffsetArguments = attr.parse_args()?;
+        let kind = match (args.nested, pointee(&field.ty)) {
+            (true, _) => FieldKind::Nested,
+            (false, Some(pointee)) => FieldKind::Pointer(Box::new(pointee)),
+            (false, None) => FieldKind::Value,
+        };
+        result.push(RemoteField {
+            name,
+            ty: field.ty.clone(),
+            offset: args.offset.base10_parse()?,
+            offset_lit: args.offset,
+            kind,
+        });
+    }
+    Ok(result)
+}
+
+pub(crate) fn derive(input: DeriveInput, krate: TokenStream) -> syn::Result<TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "RemoteStruct can not be derived for generic structs",
+        ));
+    }
+    let size = input
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("remote"))
+        .map(|attr| attr.parse_args::<RemoteArguments>())
+        .transpose()?;
+    let mut fields = parse_fields(&input)?;
+    fields.sort_by_key(|field| field.offset);
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let view = format_ident!("{}Remote", name);
+    let mem = quote!(#krate::traits::Mem);
+    let mem_error = quote!(#krate::traits::MemError);
+    let remote_ptr = quote!(#krate::structures::addr::RemotePtr);
+    let remote_struct = quote!(#krate::structures::addr::RemoteStruct);
+
+    // how many bytes every field takes up in the remote structure
+    let sizes: Vec<TokenStream> = fields
+        .iter()
+        .map(|field| {
+            let ty = &field.ty;
+            match field.kind {
+                FieldKind::Nested => quote!(<#ty as #remote_struct>::SIZE),
+                _ => quote!(::std::mem::size_of::<#ty>()),
+            }
+        })
+        .collect();
+    let ends: Vec<TokenStream> = fields
+        .iter()
+        .zip(&sizes)
+        .map(|(field, size)| {
+            let offset = &field.offset_lit;
+            quote!(#offset + #size)
+        })
+        .collect();
+
+    let struct_size = match &size {
+        Some(args) => {
+            let size = &args.size;
+            quote!(#size)
+        }
+        None => quote! {{
+            let mut size = 0;
+            #(if #ends > size { size = #ends; })*
+            size
+        }},
+    };
+
+    // whether the structure has to be read with the pointer width of this process
+    let has_pointers: Vec<TokenStream> = fields
+        .iter()
+        .filter_map(|field| {
+            let ty = &field.ty;
+            match field.kind {
+                FieldKind::Nested => Some(quote!(<#ty as #remote_struct>::HAS_POINTERS)),
+                FieldKind::Pointer(_) => Some(quote!(true)),
+                FieldKind::Value => None,
+            }
+        })
+        .collect();
+    let pointer = quote!(#krate::structures::addr::Pointer::<()>);
+
+    let mut checks = Vec::new();
+    for (i, pair) in fields.windows(2).enumerate() {
+        let end = &ends[i];
+        let next = &pair[1].offset_lit;
+        let message = format!(
+            "field `{}` of `{name}` overlaps field `{}`",
+            pair[0].name, pair[1].name
+        );
+        checks.push(quote!(::std::assert!(#end <= #next, #message);));
+    }
+    if size.is_some() {
+        for (field, end) in fields.iter().zip(&ends) {
+            let message = format!(
+                "field `{}` does not fit in the size of `{name}`",
+                field.name
+            );
+            checks.push(quote!(
+                ::std::assert!(#end <= <#name as #remote_struct>::SIZE, #message);
+            ));
+        }
+    }
+
+    let from_bytes = fields.iter().map(|field| {
+        let field_name = &field.name;
+        let ty = &field.ty;
+        let offset = &field.offset_lit;
+        match field.kind {
+            FieldKind::Nested => {
+                quote!(#field_name: <#ty as #remote_struct>::from_bytes(&bytes[#offset..]))
+            }
+            _ => quote!(#field_name: ::std::ptr::read_unaligned(bytes[#offset..].as_ptr() as *const #ty)),
+        }
+    });
+
+    let accessors = fields.iter().map(|field| {
+        let field_name = &field.name;
+        let ty = &field.ty;
+        let offset = &field.offset_lit;
+        let ptr_name = format_ident!("{}_ptr", field_name);
+        let setter = format_ident!("set_{}", field_name);
+        let ptr_doc = format!("a pointer to `{field_name}`, at offset {offset}");
+        let ptr = quote! {
+            #[doc = #ptr_doc]
+            pub fn #ptr_name(&self) -> #remote_ptr<'a, M, #ty> {
+                self.ptr.field(#offset)
+            }
+        };
+        match &field.kind {
+            FieldKind::Nested => {
+                let doc = format!("the view of `{field_name}`, at offset {offset}");
+                quote! {
+                    #ptr
+                    #[doc = #doc]
+                    pub fn #field_name(&self) -> <#ty as #remote_struct>::View<'a, M> {
+                        self.#ptr_name().view()
+                    }
+                }
+            }
+            FieldKind::Pointer(pointee) => {
+                let doc = format!("follow the pointer `{field_name}`, at offset {offset}");
+                let set_doc = format!("write the pointer `{field_name}`, at offset {offset}");
+                quote! {
+                    #ptr
+                    #[doc = #doc]
+                    /// # Safety
+                    /// This function is unsafe because it can read from any address in the process.
+                    pub unsafe fn #field_name(&self) -> ::std::result::Result<#remote_ptr<'a, M, #pointee>, #mem_error> {
+                        self.#ptr_name().deref()
+                    }
+                    #[doc = #set_doc]
+                    /// # Safety
+                    /// This function is unsafe because it can write to any address in the process.
+                    pub unsafe fn #setter(&self, value: &#ty) -> ::std::result::Result<(), #mem_error> {
+                        self.#ptr_name().write_pointer(value)
+                    }
+                }
+            }
+            FieldKind::Value => {
+                let doc = format!("read `{field_name}`, at offset {offset}");
+                let set_doc = format!("write `{field_name}`, at offset {offset}");
+                quote! {
+                    #ptr
+                    #[doc = #doc]
+                    /// # Safety
+                    /// This function is unsafe because it can read from any address in the process.
+                    pub unsafe fn #field_name(&self) -> ::std::result::Result<#ty, #mem_error> {
+                        self.#ptr_name().read()
+                    }
+                    #[doc = #set_doc]
+                    /// # Safety
+                    /// This function is unsafe because it can write to any address in the process.
+                    pub unsafe fn #setter(&self, value: &#ty) -> ::std::result::Result<(), #mem_error> {
+                        self.#ptr_name().write(value)
+                    }
+                }
+            }
+        }
+    });
+
+    let view_doc = format!("the remote view of [`{name}`], generated by `#[derive(RemoteStruct)]`");
+    Ok(quote! {
+        impl #remote_struct for #name {
+            const SIZE: usize = #struct_size;
+            const HAS_POINTERS: bool = false #(|| #has_pointers)*;
+            type View<'a, M: #mem + 'a> = #view<'a, M>;
+            fn view<'a, M: #mem + 'a>(ptr: #remote_ptr<'a, M, Self>) -> Self::View<'a, M> {
+                #view { ptr }
+            }
+            unsafe fn from_bytes(bytes: &[u8]) -> Self {
+                Self {
+                    #(#from_bytes,)*
+                }
+            }
+        }
+
+        const _: () = {
+            #(#checks)*
+        };
+
+        #[doc = #view_doc]
+        #vis struct #view<'a, M: #mem> {
+            ptr: #remote_ptr<'a, M, #name>,
+        }
+
+        impl<'a, M: #mem> ::std::clone::Clone for #view<'a, M> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+        impl<'a, M: #mem> ::std::marker::Copy for #view<'a, M> {}
+
+        impl<'a, M: #mem> #view<'a, M> {
+            /// the pointer to the structure
+            pub fn ptr(&self) -> #remote_ptr<'a, M, #name> {
+                self.ptr
+            }
+            /// read the whole structure at once, failing if it has pointers of another width than the process
+            /// # Safety
+            /// This function is unsafe because it can read from any address in the process.
+            pub unsafe fn read(&self) -> ::std::result::Result<#name, #mem_error> {
+                if <#name as #remote_struct>::HAS_POINTERS {
+                    #pointer::check_width(self.ptr.get_owner())?;
+                }
+                let bytes = #mem::read_sized(
+                    self.ptr.get_owner(),
+                    self.ptr.get_address(),
+                    <#name as #remote_struct>::SIZE,
+                )?;
+                Ok(<#name as #remote_struct>::from_bytes(&bytes))
+            }
+            #(#accessors)*
+        }
+    })
+}
@@ -0,0 +1,45 @@
+use poggers_pattern::{parse_ida, ByteSet, PatternError};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Error, LitStr};
+
+/// parse <pattern> with the same grammar as `Signature::from_ida`, failing with an error pointing into the literal
+pub(crate) fn parse(pattern: &LitStr) -> syn::Result<Vec<ByteSet>> {
+    parse_ida(&pattern.value()).map_err(|e| error(pattern, &e))
+}
+
+/// the error for <e>, pointing at the token it is about when the compiler supports it
+fn error(pattern: &LitStr, e: &PatternError) -> Error {
+    let value = pattern.value();
+    let Some(start) = e.position() else {
+        return Error::new(pattern.span(), e);
+    };
+    let end = value[start..]
+        .find(char::is_whitespace)
+        .map_or(value.len(), |len| start + len);
+    // skip the opening quote, escapes would shift the positions so only plain strings are pointed into
+    let raw = pattern.token().to_string();
+    let subspan = match raw.starts_with('"') && !raw.contains('\\') {
+        true => pattern.token().subspan(start + 1..end + 1),
+        false => None,
+    };
+    Error::new(subspan.unwrap_or(pattern.span()), e)
+}
+
+pub(crate) fn expand(pattern: LitStr, krate: TokenStream) -> syn::Result<TokenStream> {
+    let mut bytes = Vec::new();
+    let mut masks = Vec::new();
+    for (i, set) in parse(&pattern)?.iter().enumerate() {
+        let (value, mask) = set.as_masked().ok_or_else(|| {
+            let message = format!(
+                "the byte set at byte {i} can not be written as a value and mask, use `Signature::from_ida`"
+            );
+            Error::new(pattern.span(), message)
+        })?;
+        bytes.push(value);
+        masks.push(mask);
+    }
+    Ok(quote! {
+        #krate::sigscan::Pattern::new(&[#(#bytes),*], &[#(#masks),*])
+    })
+}
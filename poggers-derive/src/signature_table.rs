@@ -0,0 +1,107 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    Error, Fields, Ident, ItemStruct, LitInt, LitStr, Token,
+};
+
+/// the arguments of `#[sig("48 8B 05 ? ? ? ?", rip = 3, len = 7, offset = -2)]`
+struct SigArguments {
+    pattern: LitStr,
+    offset: isize,
+    rip: Option<usize>,
+    len: Option<usize>,
+}
+
+impl Parse for SigArguments {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = SigArguments {
+            pattern: input.parse()?,
+            offset: 0,
+            rip: None,
+            len: None,
+        };
+        while input.parse::<Option<Token![,]>>()?.is_some() {
+            if input.is_empty() {
+                break;
+            }
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let negative = input.parse::<Option<Token![-]>>()?.is_some();
+            let value: LitInt = input.parse()?;
+            match key.to_string().as_str() {
+                "offset" if negative => args.offset = -value.base10_parse::<isize>()?,
+                "offset" => args.offset = value.base10_parse()?,
+                _ if negative => return Err(Error::new(value.span(), "expected a positive value")),
+                "rip" => args.rip = Some(value.base10_parse()?),
+                "len" => args.len = Some(value.base10_parse()?),
+                _ => return Err(Error::new(key.span(), "expected `rip`, `len` or `offset`")),
+            }
+        }
+        if args.len.is_some() && args.rip.is_none() {
+            return Err(Error::new(input.span(), "`len` needs `rip` to be set"));
+        }
+        Ok(args)
+    }
+}
+
+pub(crate) fn expand(mut input: ItemStruct, krate: TokenStream) -> syn::Result<TokenStream> {
+    let Fields::Named(fields) = &mut input.fields else {
+        return Err(Error::new(
+            input.fields.span(),
+            "a signature table needs named fields",
+        ));
+    };
+    let mut names = Vec::new();
+    let mut adds = Vec::new();
+    for field in &mut fields.named {
+        let name = field.ident.clone().unwrap();
+        let position = field
+            .attrs
+            .iter()
+            .position(|attr| attr.path().is_ident("sig"))
+            .ok_or_else(|| {
+                Error::new(
+                    name.span(),
+                    format!("field `{name}` is missing its #[sig(..)]"),
+                )
+            })?;
+        let args: SigArguments = field.attrs.remove(position).parse_args()?;
+        let pattern = &args.pattern;
+        // report malformed patterns now instead of when resolving, any byte set is allowed here
+        crate::sig::parse(pattern)?;
+        let offset = args.offset;
+        // the displacement is usually the last 4 bytes of the instruction
+        let rip = match args.rip {
+            Some(rip) => {
+                let len = args.len.unwrap_or(rip + 4);
+                quote!(::std::option::Option::Some((#rip, #len)))
+            }
+            None => quote!(::std::option::Option::None),
+        };
+        adds.push(quote! {
+            table.add(::std::stringify!(#name), #pattern, #offset, #rip);
+        });
+        names.push(name);
+    }
+    let name = &input.ident;
+    let indices = 0..names.len();
+    Ok(quote! {
+        #input
+
+        impl #name {
+            /// scan <module> for every signature, reporting every signature which could not be resolved at once
+            pub fn resolve<T: #krate::sigscan::SigScan>(
+                module: &#krate::structures::modules::Module<T>,
+            ) -> ::std::result::Result<Self, #krate::sigscan::SignatureTableError> {
+                let mut table = #krate::sigscan::SignatureTable::new();
+                #(#adds)*
+                let found = table.resolve(module)?;
+                ::std::result::Result::Ok(Self {
+                    #(#names: found[#indices],)*
+                })
+            }
+        }
+    })
+}
@@ -0,0 +1,257 @@
+//! # Poggers Pattern
+//! The grammar of IDA style signatures, shared by `poggers` which parses them at runtime and `poggers-derive` which
+//! parses them at compile time, so both accept exactly the same patterns.
+//! ```
+//! use poggers_pattern::{parse_ida, ByteSet};
+//! let bytes = parse_ida("[48|4C] 8B 0? ?{2}").unwrap();
+//! assert_eq!(bytes.len(), 5);
+//! assert_eq!(bytes[1], ByteSet::exact(0x8B));
+//! assert!(bytes[0].contains(0x4C));
+//! assert_eq!(bytes[2].as_masked(), Some((0x00, 0xF0)));
+//! assert_eq!(parse_ida("[48|50]").unwrap()[0].as_masked(), None);
+//! ```
+
+#![warn(missing_docs)]
+
+use std::fmt::{self, Display};
+
+/// the longest skip allowed by `?{n}`
+pub const MAX_SKIP: usize = 0x1000;
+
+/// the bytes which match a single position of a pattern, as a bit for every byte value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ByteSet([u64; 4]);
+
+impl ByteSet {
+    /// matches every byte, `?`
+    pub const ANY: Self = Self([u64::MAX; 4]);
+    /// matches no byte, the start of a set
+    pub const NONE: Self = Self([0; 4]);
+    /// matches exactly <byte>
+    pub const fn exact(byte: u8) -> Self {
+        let mut bits = [0; 4];
+        bits[byte as usize / 64] = 1 << (byte % 64);
+        Self(bits)
+    }
+    /// matches every byte which is equal to <value> in the bits set in <mask>, e.g. `4?` is `0x40` & `0xF0`
+    pub fn masked(value: u8, mask: u8) -> Self {
+        (0..=255u8)
+            .filter(|x| x & mask == value & mask)
+            .fold(Self::NONE, |set, x| set.union(Self::exact(x)))
+    }
+    /// matches the bytes of both
+    pub const fn union(self, other: Self) -> Self {
+        let (a, b) = (self.0, other.0);
+        Self([a[0] | b[0], a[1] | b[1], a[2] | b[2], a[3] | b[3]])
+    }
+    /// is <byte> allowed
+    pub const fn contains(&self, byte: u8) -> bool {
+        self.0[byte as usize / 64] & (1 << (byte % 64)) != 0
+    }
+    /// is every byte allowed
+    pub fn is_any(&self) -> bool {
+        *self == Self::ANY
+    }
+    /// the byte if exactly one byte is allowed
+    pub fn as_exact(&self) -> Option<u8> {
+        let mut values = self.values();
+        let first = values.next()?;
+        values.next().is_none().then_some(first)
+    }
+    /// every allowed byte, in ascending order
+    pub fn values(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..=255u8).filter(|x| self.contains(*x))
+    }
+    /// the bit of every byte value, byte `n` is bit `n % 64` of word `n / 64`
+    pub const fn bits(&self) -> [u64; 4] {
+        self.0
+    }
+    /// the value and mask which match exactly the allowed bytes, if the set can be written that way.
+    /// `[48|4C]` is `0x48` & `0xFB`, but `[48|50]` has no value and mask.
+    pub fn as_masked(&self) -> Option<(u8, u8)> {
+        let first = (0..=255u8).find(|x| self.contains(*x))?;
+        let differ = (0..=255u8)
+            .filter(|x| self.contains(*x))
+            .fold(0, |bits, x| bits | (x ^ first));
+        let (value, mask) = (first & !differ, !differ);
+        (*self == Self::masked(value, mask)).then_some((value, mask))
+    }
+}
+
+impl Display for ByteSet {
+    /// formats as `?`, `48`, `4?`, `?B` or a set such as `[48|4C]`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_any() {
+            return write!(f, "?");
+        }
+        if let Some(byte) = self.as_exact() {
+            return write!(f, "{:02X}", byte);
+        }
+        let first = self.values().next().unwrap_or_default();
+        if *self == Self::masked(first, 0xF0) {
+            return write!(f, "{:X}?", first >> 4);
+        }
+        if *self == Self::masked(first, 0x0F) {
+            return write!(f, "?{:X}", first & 0xF);
+        }
+        write!(f, "[")?;
+        let mut high = None;
+        for (i, byte) in self.values().enumerate() {
+            // whole high nibbles were already written as `X?`
+            if high == Some(byte >> 4) {
+                continue;
+            }
+            if i != 0 {
+                write!(f, "|")?;
+            }
+            if byte & 0xF == 0 && Self::masked(byte, 0xF0).union(*self) == *self {
+                high = Some(byte >> 4);
+                write!(f, "{:X}?", byte >> 4)?;
+            } else {
+                write!(f, "{:02X}", byte)?;
+            }
+        }
+        write!(f, "]")
+    }
+}
+
+/// Pattern parsing failures, each carrying the position in the pattern where parsing failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternError {
+    /// the pattern contains no bytes
+    Empty,
+    /// a byte was not valid hex
+    InvalidByte(usize, String),
+    /// a byte was missing its second hex digit
+    IncompleteByte(usize),
+    /// a `?{n}` skip did not have a count between 1 and [`MAX_SKIP`]
+    InvalidSkip(usize),
+    /// a `[..|..]` byte set was not closed
+    UnclosedSet(usize),
+}
+
+impl PatternError {
+    /// the position in the pattern where parsing failed, none for an empty pattern
+    pub fn position(&self) -> Option<usize> {
+        match self {
+            Self::Empty => None,
+            Self::InvalidByte(pos, _)
+            | Self::IncompleteByte(pos)
+            | Self::InvalidSkip(pos)
+            | Self::UnclosedSet(pos) => Some(*pos),
+        }
+    }
+}
+
+impl Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "signature is empty"),
+            Self::InvalidByte(pos, byte) => write!(f, "invalid byte '{byte}' at {pos}"),
+            Self::IncompleteByte(pos) => write!(f, "incomplete byte at {pos}"),
+            Self::InvalidSkip(pos) => write!(
+                f,
+                "invalid skip at {pos}, expected ?{{n}} with n from 1 to {MAX_SKIP}"
+            ),
+            Self::UnclosedSet(pos) => write!(f, "unclosed byte set at {pos}"),
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+/// parse an IDA (`48 8B ? ?`) or x64dbg (`48 8B ?? ??`) style pattern into the bytes every position matches.
+/// tokens with more than one byte (`488B`) are split into bytes. on top of that the following are supported:
+/// * half byte wildcards, `4?` matches `40` to `4F` and `?B` matches `0B` to `FB`
+/// * byte sets, `[48|4C]` matches either `48` or `4C`, the bytes in a set may have half byte wildcards
+/// * skips, `?{4}` is the same as `? ? ? ?`
+pub fn parse_ida(pattern: &str) -> Result<Vec<ByteSet>, PatternError> {
+    let mut bytes = Vec::with_capacity(pattern.len() / 2);
+    for token in pattern.split_whitespace() {
+        // position of the token within the pattern
+        let pos = offset_in(pattern, token);
+        if token == "?" || token == "??" {
+            bytes.push(ByteSet::ANY);
+            continue;
+        }
+        if let Some(count) = token.strip_prefix("?{") {
+            let count = count
+                .strip_suffix('}')
+                .and_then(|x| x.parse::<usize>().ok())
+                .filter(|x| (1..=MAX_SKIP).contains(x))
+                .ok_or(PatternError::InvalidSkip(pos))?;
+            bytes.extend(std::iter::repeat_n(ByteSet::ANY, count));
+            continue;
+        }
+        if let Some(set) = token.strip_prefix('[') {
+            let set = set
+                .strip_suffix(']')
+                .ok_or(PatternError::UnclosedSet(pos))?;
+            let mut allowed = ByteSet::NONE;
+            for byte in set.split('|') {
+                let at = offset_in(pattern, byte);
+                if byte.len() < 2 {
+                    return Err(PatternError::IncompleteByte(at));
+                }
+                if byte.len() > 2 {
+                    return Err(PatternError::InvalidByte(at, byte.to_string()));
+                }
+                allowed = allowed.union(parse_byte(pattern, at)?);
+            }
+            bytes.push(allowed);
+            continue;
+        }
+        for (i, chunk) in token.as_bytes().chunks(2).enumerate() {
+            let at = pos + i * 2;
+            if chunk.len() != 2 {
+                return Err(PatternError::IncompleteByte(at));
+            }
+            bytes.push(parse_byte(pattern, at)?);
+        }
+    }
+    if bytes.is_empty() {
+        return Err(PatternError::Empty);
+    }
+    Ok(bytes)
+}
+
+/// the position of <part> within <pattern>, <part> has to be a slice of <pattern>
+fn offset_in(pattern: &str, part: &str) -> usize {
+    part.as_ptr() as usize - pattern.as_ptr() as usize
+}
+
+/// parses the two hex digits at <at> in <pattern>, either digit may be a `?` wildcard
+fn parse_byte(pattern: &str, at: usize) -> Result<ByteSet, PatternError> {
+    let (mut value, mut mask) = (0u8, 0u8);
+    for (i, digit) in pattern.as_bytes()[at..].iter().take(2).enumerate() {
+        let shift = 4 - i * 4;
+        if *digit == b'?' {
+            continue;
+        }
+        let digit = (*digit as char)
+            .to_digit(16)
+            .ok_or_else(|| invalid_byte(pattern, at))?;
+        value |= (digit as u8) << shift;
+        mask |= 0xF << shift;
+    }
+    Ok(ByteSet::masked(value, mask))
+}
+
+/// parses the two hex digits at <at> in <pattern>, neither digit may be a wildcard
+pub fn parse_hex(pattern: &str, at: usize) -> Result<u8, PatternError> {
+    let invalid = || invalid_byte(pattern, at);
+    let digits = pattern.get(at..at + 2).ok_or_else(invalid)?;
+    if !digits.bytes().all(|x| x.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    u8::from_str_radix(digits, 16).map_err(|_| invalid())
+}
+
+/// the error for an invalid byte at <at> in <pattern>
+fn invalid_byte(pattern: &str, at: usize) -> PatternError {
+    let end = pattern.len().min(at + 2);
+    PatternError::InvalidByte(
+        at,
+        String::from_utf8_lossy(&pattern.as_bytes()[at..end]).into(),
+    )
+}
@@ -18,21 +18,31 @@
 //!  ## Common Structs
 //!  * [`Process`](structures::process::Process) - A struct which holds the handle to a process.
 //!  * [`Module`](structures::modules::Module) - A struct which holds the handle to a module.
+//!  * [`MemoryRegion`](structures::regions::MemoryRegion) - A mapped region of memory in a process, see [`Mem::regions`](traits::Mem::regions).
+//!  * [`Signature`](sigscan::Signature) - A pre-compiled signature, parsed from IDA, x64dbg or code style patterns.
+//!  * [`Pattern`](sigscan::Pattern) - A signature parsed and checked at compile time by `sig!` from `poggers-derive`.
+//!  * [`RegionFilter`](structures::regions::RegionFilter) - Selects which regions of a process are scanned by [`SigScan::scan_regions`](sigscan::SigScan::scan_regions).
+//!  * [`SignatureSet`](sigscan::SignatureSet) - Named signatures which are all resolved in a single pass over a module or process.
+//!  * [`TextPattern`](sigscan::TextPattern) - An ASCII, UTF-8 or UTF-16 string to scan for, optionally ignoring case.
+//!  * [`ValueScanner`](sigscan::ValueScanner) - A Cheat Engine style value scan session with first and next scans.
+//!  * [`PointerScanner`](sigscan::PointerScanner) - Finds chains of pointers from modules to an address which survive restarts.
+//!  * [`ReadBatch`](structures::batch::ReadBatch) - Many reads which are submitted at once, with a single syscall where the platform allows it.
 //!  * [`ToolSnapshot`](structures::create_snapshot::ToolSnapshot) - A wrapper around the ToolHelp32Snapshot function.
 //!  ## Common Traits
 //!  * [`Mem`](traits::Mem) - A trait which allows a struct to read and write to memory.
 //!  * [`SigScan`](sigscan::SigScan) - A trait which allows a struct to sig scan.
+//!  * [`Pod`](structures::pod::Pod) - Plain old data, which can be read with [`Mem::read_pod`](traits::Mem::read_pod) from any bytes.
 //!  ## Example External usage:
-//! ```
+//! ```no_run
 //!  use poggers::structures::process::Process;
 //!  use poggers::traits::Mem;
-//!  let process = Process::find_name("csgo.exe")?;
+//!  let process = Process::find_name("csgo.exe").unwrap();
 //!  unsafe {
 //!     process.write(0x1000,&1).unwrap()
 //!  }
 //! ```
 //!  ## Example Internal usage:
-//! ```
+//! ```ignore
 //! use poggers::structures::process::implement::utils::ProcessUtils;
 //! use poggers::structures::process::Process;
 //! use poggers::traits::Mem;
@@ -42,7 +52,7 @@
 //! fn entry() {
 //!     let this_proc = Process::this_process();
 //!     unsafe {
-//!         let bleh : i32 = this_proc.read(0x1000).?;
+//!         let bleh : i32 = this_proc.read(0x1000).unwrap();
 //!         println!("{}",bleh);
 //!         let base_mod_name = this_proc.get_base_module().unwrap().get_name();
 //!         println!("{}",base_mod_name);
@@ -0,0 +1,230 @@
+use std::ops::Range;
+
+use crate::{
+    structures::regions::{MemoryRegion, RegionFilter},
+    traits::{Mem, MemError},
+};
+
+/// how much is read from the process at a time
+pub(crate) const CHUNK_SIZE: usize = 0x10_0000;
+const PAGE_SIZE: usize = 0x1000;
+
+/// merges the readable <regions> into runs of contiguous readable memory
+pub(crate) fn readable_runs(regions: impl Iterator<Item = MemoryRegion>) -> Vec<Range<usize>> {
+    let mut runs: Vec<Range<usize>> = Vec::new();
+    for region in regions.filter(|region| region.get_protections().is_readable()) {
+        match runs.last_mut() {
+            Some(run) if run.end == region.start => run.end = region.end,
+            _ => runs.push(region.start..region.end),
+        }
+    }
+    runs
+}
+
+/// the runs of readable memory in <mem> selected by <filter>
+pub(crate) fn filtered_runs<M: Mem>(
+    mem: &M,
+    filter: &RegionFilter,
+) -> Result<Vec<Range<usize>>, MemError> {
+    Ok(readable_runs(
+        mem.regions()?.filter_map(|region| filter.apply(region)),
+    ))
+}
+
+/// reads runs of memory in chunks of at most [`CHUNK_SIZE`].
+/// every chunk starts with the last <overlap> bytes of the previous chunk when they are contiguous,
+/// so a match of up to <overlap> + 1 bytes which straddles two chunks is found exactly once.
+pub(crate) struct ChunkReader<'a, M: Mem> {
+    mem: &'a M,
+    runs: std::vec::IntoIter<Range<usize>>,
+    /// the part of the current run which has not been read yet
+    current: Range<usize>,
+    /// is the end of <buf> directly before <current>
+    contiguous: bool,
+    overlap: usize,
+    buf: Vec<u8>,
+}
+
+impl<'a, M: Mem> ChunkReader<'a, M> {
+    pub(crate) fn new(mem: &'a M, runs: Vec<Range<usize>>, overlap: usize) -> Self {
+        Self {
+            mem,
+            runs: runs.into_iter(),
+            current: 0..0,
+            contiguous: false,
+            overlap,
+            buf: Vec::new(),
+        }
+    }
+    /// reads the regions of <mem> selected by <filter>
+    pub(crate) fn filtered(
+        mem: &'a M,
+        filter: &RegionFilter,
+        overlap: usize,
+    ) -> Result<Self, MemError> {
+        Ok(Self::new(mem, filtered_runs(mem, filter)?, overlap))
+    }
+    /// read the next chunk, returns the address of the chunk and its bytes.
+    /// pages within a run which fault are skipped, other read errors are returned.
+    pub(crate) fn next_chunk(&mut self) -> Result<Option<(usize, &[u8])>, MemError> {
+        loop {
+            if self.current.is_empty() {
+                let Some(run) = self.runs.next() else {
+                    return Ok(None);
+                };
+                self.current = run;
+                self.contiguous = false;
+            }
+            let keep = if self.contiguous {
+                self.overlap.min(self.buf.len())
+            } else {
+                0
+            };
+            self.buf.drain(..self.buf.len() - keep);
+
+            let addr = self.current.start;
+            let size = CHUNK_SIZE.min(self.current.len());
+            self.buf.resize(keep + size, 0);
+            let read = match unsafe { self.mem.read_partial(addr, &mut self.buf[keep..]) } {
+                Ok(read) => read,
+                Err(e) if e.is_fault() => 0,
+                Err(e) => return Err(e),
+            };
+            self.buf.truncate(keep + read);
+            self.contiguous = read == size;
+            if read == size {
+                self.current.start += size;
+            } else {
+                // skip past the page which could not be read
+                let resume = (addr + read + 1).next_multiple_of(PAGE_SIZE);
+                self.current.start = resume.min(self.current.end);
+            }
+            if read != 0 {
+                return Ok(Some((addr - keep, &self.buf)));
+            }
+        }
+    }
+    /// lazily runs <find> over every chunk, <find> gets the address of the chunk and its bytes
+    /// and returns the addresses it found. the iterator ends after the first read error.
+    pub(crate) fn find_all<F>(
+        mut self,
+        mut find: F,
+    ) -> impl Iterator<Item = Result<usize, MemError>> + 'a
+    where
+        F: FnMut(usize, &[u8]) -> Vec<usize> + 'a,
+    {
+        let mut pending = Vec::new().into_iter();
+        let mut failed = false;
+        std::iter::from_fn(move || loop {
+            if let Some(found) = pending.next() {
+                return Some(Ok(found));
+            }
+            if failed {
+                return None;
+            }
+            match self.next_chunk() {
+                Ok(Some((addr, chunk))) => pending = find(addr, chunk).into_iter(),
+                Ok(None) => return None,
+                Err(e) => {
+                    failed = true;
+                    return Some(Err(e));
+                }
+            }
+        })
+    }
+}
+
+/// the raw bytes of <val>
+pub(crate) fn value_bytes<V>(val: &V) -> Vec<u8> {
+    let size = std::mem::size_of::<V>();
+    let mut bytes = vec![0; size];
+    unsafe { (val as *const V as *const u8).copy_to_nonoverlapping(bytes.as_mut_ptr(), size) };
+    bytes
+}
+
+/// finds every address in <data> (which starts at <addr>) aligned to <align> that holds <value>
+pub(crate) fn find_values(value: &[u8], align: usize, addr: usize, data: &[u8]) -> Vec<usize> {
+    if value.is_empty() || data.len() < value.len() {
+        return Vec::new();
+    }
+    let first = addr.next_multiple_of(align) - addr;
+    (first..=data.len() - value.len())
+        .step_by(align)
+        .filter(|&i| &data[i..i + value.len()] == value)
+        .map(|i| addr + i)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::find_values;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_chunks_overlap() {
+        use super::{ChunkReader, CHUNK_SIZE};
+        use crate::structures::process::Process;
+
+        let this = Process::this_process();
+        let data: Vec<u8> = (0..CHUNK_SIZE * 2 + 0x123).map(|x| x as u8).collect();
+        let start = data.as_ptr() as usize;
+        let mut reader = ChunkReader::new(
+            &this,
+            Vec::from_iter(std::iter::once(start..start + data.len())),
+            3,
+        );
+
+        let mut chunks = Vec::new();
+        while let Some((addr, chunk)) = reader.next_chunk().unwrap() {
+            assert_eq!(chunk, &data[addr - start..addr - start + chunk.len()]);
+            chunks.push((addr, chunk.len()));
+        }
+        assert_eq!(
+            chunks,
+            [
+                (start, CHUNK_SIZE),
+                (start + CHUNK_SIZE - 3, CHUNK_SIZE + 3),
+                (start + CHUNK_SIZE * 2 - 3, 0x123 + 3)
+            ]
+        );
+    }
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_chunks_read_error() {
+        use super::{filtered_runs, ChunkReader};
+        use crate::structures::{process::Process, regions::RegionFilter};
+
+        let mut child = std::process::Command::new("sleep")
+            .arg("10")
+            .spawn()
+            .unwrap();
+        let ex = Process::find_pid(child.id()).unwrap();
+        let runs = filtered_runs(&ex, &RegionFilter::new()).unwrap();
+        child.kill().unwrap();
+        child.wait().unwrap();
+        // reading a process which has exited is not an unreadable page, so it is not skipped
+        let mut reader = ChunkReader::new(&ex, runs, 0);
+        let err = reader.next_chunk().unwrap_err();
+        assert!(!err.is_fault(), "{err}");
+    }
+    #[test]
+    fn test_find_values_aligned() {
+        let data = [0, 1, 2, 1, 2, 0, 1, 2];
+        assert_eq!(find_values(&[1, 2], 2, 0x1001, &data), [0x1002, 0x1004]);
+        assert_eq!(
+            find_values(&[1, 2], 1, 0x1000, &data),
+            [0x1001, 0x1003, 0x1006]
+        );
+        assert!(find_values(&[1, 2], 2, 0x1001, &data[..1]).is_empty());
+    }
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_runs_merge() {
+        use super::readable_runs;
+        use crate::{structures::process::Process, traits::Mem};
+
+        let this = Process::this_process();
+        let runs = readable_runs(this.regions().unwrap());
+        assert!(runs.windows(2).all(|x| x[0].end < x[1].start));
+    }
+}
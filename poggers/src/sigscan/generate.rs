@@ -0,0 +1,170 @@
+use std::ops::Range;
+
+use poggers_pattern::ByteSet;
+
+use super::Signature;
+use crate::traits::{Mem, MemError};
+
+/// how many exact bytes the first scan of the module uses, shorter prefixes are only scanned when this is unique
+const FIRST_SCAN_EXACT: usize = 6;
+
+/// Failures when generating a signature
+#[derive(Debug, thiserror::Error)]
+pub enum GenerateError {
+    /// the address is not inside the module
+    #[error("[{0:X}] is not inside the module")]
+    OutOfModule(usize),
+    /// there was no unique signature of at most [`Signature::MAX_GENERATED`] bytes
+    #[error("no unique signature for [{0:X}] within {max} bytes", max = Signature::MAX_GENERATED)]
+    NotUnique(usize),
+    /// reading the module failed
+    #[error(transparent)]
+    Mem(#[from] MemError),
+}
+
+/// marks the bytes of <code> (which starts at <addr>) which are likely to change between builds, being absolute
+/// pointers of <width> bytes into <module>, relative branches and RIP relative displacements.
+pub(crate) fn volatile_bytes(
+    code: &[u8],
+    addr: usize,
+    module: &Range<usize>,
+    width: usize,
+) -> Vec<bool> {
+    let mut volatile = vec![false; code.len()];
+    let mut mark = |range: Range<usize>| volatile[range].iter_mut().for_each(|x| *x = true);
+    let rel32 = |at: usize| {
+        let disp = i32::from_le_bytes(code.get(at..at + 4)?.try_into().ok()?);
+        let target = (addr + at + 4).wrapping_add_signed(disp as isize);
+        module.contains(&target).then_some(at..at + 4)
+    };
+    for i in 0..code.len() {
+        let ptr = match code.get(i..i + width) {
+            Some(ptr) if width == 4 => Some(u32::from_le_bytes(ptr.try_into().unwrap()) as usize),
+            Some(ptr) => Some(usize::from_le_bytes(ptr.try_into().unwrap())),
+            None => None,
+        };
+        if ptr.is_some_and(|ptr| module.contains(&ptr)) {
+            mark(i..i + width);
+        }
+        let disp = match (code[i], code.get(i + 1)) {
+            // call / jmp rel32
+            (0xE8 | 0xE9, _) => rel32(i + 1),
+            // jcc rel32
+            (0x0F, Some(0x80..=0x8F)) => rel32(i + 2),
+            // a ModRM byte addressing [rip + disp32]
+            (modrm, _) if i != 0 && modrm & 0xC7 == 0x05 => rel32(i + 1),
+            _ => None,
+        };
+        if let Some(disp) = disp {
+            mark(disp);
+        }
+    }
+    volatile
+}
+
+/// generates the shortest signature for <addr> which only matches once in <module>
+pub(crate) fn generate<M: Mem>(
+    mem: &M,
+    module: Range<usize>,
+    addr: usize,
+    scan_all: impl Fn(&Signature) -> Result<Vec<usize>, MemError>,
+) -> Result<Signature, GenerateError> {
+    if !module.contains(&addr) {
+        return Err(GenerateError::OutOfModule(addr));
+    }
+    let code = read_clipped(mem, addr, &module)?;
+    let pattern: Vec<ByteSet> = volatile_bytes(&code, addr, &module, mem.pointer_width())
+        .iter()
+        .zip(&code)
+        .map(|(volatile, byte)| match volatile {
+            true => ByteSet::ANY,
+            false => ByteSet::exact(*byte),
+        })
+        .collect();
+    let prefix = |len: usize| Signature::from_bytes(pattern[..len].to_vec());
+    // ends of the prefixes which end with an exact byte, shorter ones are never needed
+    let ends: Vec<usize> = (1..=pattern.len())
+        .filter(|&len| !pattern[len - 1].is_any())
+        .collect();
+    let Some(&first) = ends.get(FIRST_SCAN_EXACT - 1).or(ends.last()) else {
+        return Err(GenerateError::NotUnique(addr));
+    };
+
+    // every other match of the first prefix, any longer prefix can only match at these
+    let others: Vec<usize> = scan_all(&prefix(first))?
+        .into_iter()
+        .filter(|x| *x != addr)
+        .collect();
+    if others.is_empty() {
+        // try shorter prefixes, once one is not unique neither is anything shorter
+        let mut len = first;
+        for &shorter in ends.iter().rev().skip_while(|x| **x >= first) {
+            if scan_all(&prefix(shorter))? != [addr] {
+                break;
+            }
+            len = shorter;
+        }
+        return Ok(prefix(len));
+    }
+    // the prefix has to reach past the first byte which differs at every other match
+    let mut needed = first;
+    for other in others {
+        let data = read_clipped(mem, other, &module)?;
+        let differs = pattern
+            .iter()
+            .enumerate()
+            .find(|(i, byte)| data.get(*i).is_none_or(|x| !byte.contains(*x)))
+            .map(|(i, _)| i + 1)
+            .ok_or(GenerateError::NotUnique(addr))?;
+        needed = needed.max(differs);
+    }
+    let len = ends
+        .iter()
+        .find(|x| **x >= needed)
+        .ok_or(GenerateError::NotUnique(addr))?;
+    Ok(prefix(*len))
+}
+
+/// reads up to [`Signature::MAX_GENERATED`] bytes at <addr>, without going past the end of <module>
+fn read_clipped<M: Mem>(mem: &M, addr: usize, module: &Range<usize>) -> Result<Vec<u8>, MemError> {
+    let mut data = vec![0; Signature::MAX_GENERATED.min(module.end - addr)];
+    let read = unsafe { mem.read_partial(addr, &mut data)? };
+    data.truncate(read);
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::volatile_bytes;
+
+    #[test]
+    fn test_volatile_bytes() {
+        let module = 0x1000_0000..0x1001_0000;
+        let addr = 0x1000_1000;
+        // call +0x10, mov rax, [rip + 0x100], mov eax, 5, jz +0x20
+        let code = [
+            0xE8, 0x10, 0, 0, 0, 0x48, 0x8B, 0x05, 0, 1, 0, 0, 0xB8, 5, 0, 0, 0, 0x0F, 0x84, 0x20,
+            0, 0, 0,
+        ];
+        let volatile = volatile_bytes(&code, addr, &module, 8);
+        let expected = [
+            false, true, true, true, true, false, false, false, true, true, true, true, false,
+            false, false, false, false, false, false, true, true, true, true,
+        ];
+        assert_eq!(volatile, expected);
+
+        // a call out of the module and an absolute pointer into it
+        let mut code = vec![0xE8, 0, 0, 0, 0x80];
+        code.extend_from_slice(&0x1000_2000u64.to_le_bytes());
+        let volatile = volatile_bytes(&code, addr, &module, 8);
+        assert!(volatile[..5].iter().all(|x| !x));
+        assert!(volatile[5..].iter().all(|x| *x));
+
+        // a 32 bit process has 4 byte pointers
+        let mut code = vec![0x68];
+        code.extend_from_slice(&0x1000_2000u32.to_le_bytes());
+        code.extend_from_slice(&[0xC3, 0, 0, 0, 0]);
+        let volatile = volatile_bytes(&code, addr, &module, 4);
+        assert_eq!(volatile[..6], [false, true, true, true, true, false]);
+    }
+}
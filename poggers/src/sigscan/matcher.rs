@@ -0,0 +1,206 @@
+use poggers_pattern::ByteSet;
+
+/// a compiled pattern, searched for by running horspool over the longest run of exact bytes (the anchor)
+/// and then verifying the rest of the pattern around each anchor hit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Matcher {
+    /// the pattern
+    bytes: Vec<ByteSet>,
+    /// offset of the anchor into the pattern
+    anchor_off: usize,
+    /// length of the anchor
+    anchor_len: usize,
+    /// horspool shift table for the anchor
+    shift: [usize; 256],
+}
+
+impl Matcher {
+    /// compiles a pattern
+    pub(crate) fn new(bytes: Vec<ByteSet>) -> Self {
+        let (mut anchor_off, mut anchor_len) = (0, 0);
+        let mut run_start = 0;
+        for (i, byte) in bytes.iter().enumerate() {
+            if byte.as_exact().is_none() {
+                run_start = i + 1;
+            } else if i + 1 - run_start > anchor_len {
+                anchor_off = run_start;
+                anchor_len = i + 1 - run_start;
+            }
+        }
+        let mut shift = [anchor_len.max(1); 256];
+        for (i, byte) in bytes[anchor_off..anchor_off + anchor_len]
+            .iter()
+            .enumerate()
+            .take(anchor_len.saturating_sub(1))
+        {
+            shift[byte.as_exact().unwrap() as usize] = anchor_len - 1 - i;
+        }
+        Self {
+            bytes,
+            anchor_off,
+            anchor_len,
+            shift,
+        }
+    }
+    /// the pattern
+    pub(crate) fn bytes(&self) -> &[ByteSet] {
+        &self.bytes
+    }
+    /// the length of the pattern in bytes
+    pub(crate) fn len(&self) -> usize {
+        self.bytes.len()
+    }
+    /// does the pattern match <data> at the start
+    fn matches_at(&self, data: &[u8]) -> bool {
+        self.bytes
+            .iter()
+            .zip(data)
+            .all(|(pattern, byte)| pattern.contains(*byte))
+    }
+    /// find the offset of the first match in <data>
+    pub(crate) fn find(&self, data: &[u8]) -> Option<usize> {
+        self.find_from(data, 0)
+    }
+    /// find the offset of every match in <data>, including overlapping ones
+    pub(crate) fn find_all<'a>(&'a self, data: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        let mut from = 0;
+        std::iter::from_fn(move || {
+            let found = self.find_from(data, from)?;
+            from = found + 1;
+            Some(found)
+        })
+    }
+    /// find the offset of the first match in <data> which starts at or after <from>
+    fn find_from(&self, data: &[u8], from: usize) -> Option<usize> {
+        let len = self.len();
+        if len == 0 || data.len() < len {
+            return None;
+        }
+        let last_start = data.len() - len;
+        if from > last_start {
+            return None;
+        }
+        if self.anchor_len == 0 {
+            // no exact bytes to search for, check every position
+            return (from..=last_start).find(|&start| self.matches_at(&data[start..start + len]));
+        }
+        let anchor = &self.bytes[self.anchor_off..self.anchor_off + self.anchor_len];
+        let mut start = from;
+        while start <= last_start {
+            let window = &data[start + self.anchor_off..start + self.anchor_off + self.anchor_len];
+            if anchor
+                .iter()
+                .rev()
+                .zip(window.iter().rev())
+                .all(|(pattern, byte)| pattern.contains(*byte))
+                && self.matches_at(&data[start..start + len])
+            {
+                return Some(start);
+            }
+            start += self.shift[window[self.anchor_len - 1] as usize];
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use poggers_pattern::ByteSet;
+
+    use super::Matcher;
+    use crate::sigscan::Signature;
+
+    fn parse(pattern: &str) -> Matcher {
+        Signature::from_ida(pattern).unwrap().matcher
+    }
+
+    fn naive(pattern: &[ByteSet], data: &[u8]) -> Option<usize> {
+        (0..(data.len() + 1).saturating_sub(pattern.len()))
+            .find(|&i| pattern.iter().zip(&data[i..]).all(|(p, b)| p.contains(*b)))
+    }
+
+    #[test]
+    fn test_repeating_prefix() {
+        let matcher = parse("48 48 8B");
+        assert_eq!(matcher.find(&[0x48, 0x48, 0x48, 0x8B]), Some(1));
+        let matcher = parse("48 ? 48 8B");
+        assert_eq!(matcher.find(&[0x48, 0x48, 0x48, 0x48, 0x8B]), Some(1));
+    }
+    #[test]
+    fn test_wildcards() {
+        let matcher = parse("E8 ? ? ?? ? C3");
+        assert_eq!(matcher.find(&[0x90, 0xE8, 1, 2, 3, 4, 0xC3]), Some(1));
+        let matcher = parse("? ?");
+        assert_eq!(matcher.find(&[0x90, 0x90]), Some(0));
+        assert_eq!(matcher.find(&[0x90]), None);
+    }
+    #[test]
+    fn test_match_at_end() {
+        let matcher = parse("8B05");
+        assert_eq!(matcher.find(&[0, 0, 0, 0x8B, 0x05]), Some(3));
+        assert_eq!(matcher.find(&[0, 0, 0, 0x8B]), None);
+    }
+    #[test]
+    fn test_find_all() {
+        let matcher = parse("48 48");
+        let data = [0x48, 0x48, 0x48, 0x90, 0x48, 0x48];
+        assert_eq!(matcher.find_all(&data).collect::<Vec<_>>(), [0, 1, 4]);
+        let matcher = parse("?");
+        assert_eq!(matcher.find_all(&data[..3]).collect::<Vec<_>>(), [0, 1, 2]);
+        assert_eq!(matcher.find_all(&[]).count(), 0);
+    }
+    #[test]
+    fn test_nibbles_and_sets() {
+        let matcher = parse("E8 4? ?B [48|4C] ?{2} C3");
+        assert_eq!(matcher.len(), 7);
+        assert_eq!(matcher.find(&[0xE8, 0x41, 0x5B, 0x4C, 1, 2, 0xC3]), Some(0));
+        assert_eq!(matcher.find(&[0xE8, 0x51, 0x5B, 0x4C, 1, 2, 0xC3]), None);
+        assert_eq!(matcher.find(&[0xE8, 0x41, 0x5C, 0x4C, 1, 2, 0xC3]), None);
+        assert_eq!(matcher.find(&[0xE8, 0x41, 0x5B, 0x49, 1, 2, 0xC3]), None);
+        // no exact bytes at all
+        let matcher = parse("4? [01|02]");
+        assert_eq!(matcher.find(&[0x48, 0x48, 0x02]), Some(1));
+        assert_eq!(matcher.find(&[0x48, 0x03]), None);
+    }
+    #[test]
+    fn test_display() {
+        let formatted = ["?", "48", "4?", "?B", "[48|4C]", "[4?|50]", "[48|5?|C3]"];
+        for x in formatted {
+            assert_eq!(parse(x).bytes()[0].to_string(), x);
+        }
+    }
+    #[test]
+    fn test_against_naive() {
+        // small alphabet so that partial matches and repeats are common
+        let mut seed = 0x2545F491u32;
+        let mut next = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            seed
+        };
+        for _ in 0..2000 {
+            let data: Vec<u8> = (0..next() % 64).map(|_| (next() % 3) as u8).collect();
+            let pattern: Vec<ByteSet> = (0..1 + next() % 6)
+                .map(|_| match next() % 6 {
+                    0 => ByteSet::ANY,
+                    // only the low bit has to match
+                    4 => ByteSet::masked(next() as u8 % 3, 0x01),
+                    5 => ByteSet::exact(0).union(ByteSet::exact(2)),
+                    x => ByteSet::exact((x % 3) as u8),
+                })
+                .collect();
+            let matcher = Matcher::new(pattern.clone());
+            assert_eq!(
+                matcher.find(&data),
+                naive(&pattern, &data),
+                "{pattern:?} in {data:?}"
+            );
+            let all = matcher.find_all(&data).collect::<Vec<_>>();
+            let naive_all = (0..data.len())
+                .filter(|&i| naive(&pattern, &data[i..]) == Some(0))
+                .collect::<Vec<_>>();
+            assert_eq!(all, naive_all, "{pattern:?} in {data:?}");
+        }
+    }
+}
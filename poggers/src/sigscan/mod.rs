@@ -0,0 +1,315 @@
+use std::borrow::Cow;
+
+use super::{
+    structures::regions::RegionFilter,
+    traits::{Mem, MemError},
+};
+
+pub(crate) mod chunks;
+pub(crate) mod generate;
+mod matcher;
+mod parallel;
+mod pattern;
+mod pointer_scanner;
+mod set;
+mod signature;
+mod strings;
+mod table;
+mod value_scanner;
+use chunks::{filtered_runs, ChunkReader};
+pub use generate::GenerateError;
+pub use pattern::Pattern;
+pub use pointer_scanner::{PointerChain, PointerScanError, PointerScanResults, PointerScanner};
+pub use set::{SetMatchError, SetMatches, SignatureSet};
+pub use signature::{AsSignature, Signature, SignatureError};
+pub use strings::{FoundString, StringEncoding, TextPattern};
+pub use table::{SignatureFailure, SignatureTable, SignatureTableError};
+pub use value_scanner::{FirstScan, NextScan, ScanValue, ValueScanError, ValueScanner};
+
+/// The trait which allows a class to sig scan.
+/// # Notes
+/// Requires the [`Mem`] trait to be implemented.
+/// # Functions
+/// * [`SigScan::scan`] / [`SigScan::scan_slice`] scan for an IDA style signature, e.g. `48 8B ? ? 05`
+/// * [`SigScan::scan_batch_value`] scan for a value instead of a signature (not really recommended
+///   unless u know what you are doing)
+/// * [`SigScan::scan_all`] / [`SigScan::scan_batch_value_all`] the same as above, but for every match
+/// * [`SigScan::scan_regions`] / [`SigScan::scan_regions_all`] scan every region of the process selected by a
+///   [`RegionFilter`], including memory outside of any module
+/// * [`SigScan::par_scan_regions`] / [`SigScan::par_scan_regions_all`] the same as above, but split across threads
+/// * [`SigScan::scan_set`] scan for every signature in a [`SignatureSet`] in a single pass
+/// * [`SigScan::strings`] list the printable strings in memory, use a [`TextPattern`] to scan for a known one
+pub trait SigScan: Mem {
+    /// Scans for a pattern in the process.
+    /// # Arguments
+    /// * `pattern` - The pattern to scan for, either a pattern string or a [`Signature`].
+    /// * `iter` the iterator to scan
+    /// # Returns
+    /// * [Option<usize>] - The address which has been found.
+    fn scan<'a>(
+        &self,
+        pattern: impl AsSignature,
+        iter: impl Iterator<Item = &'a u8>,
+    ) -> Option<usize> {
+        let data: Vec<u8> = iter.copied().collect();
+        self.scan_slice(pattern, &data)
+    }
+    /// Scans for a pattern in a slice of bytes, avoids copying the bytes like [`SigScan::scan`] has to.
+    /// # Arguments
+    /// * `pattern` - The pattern to scan for, either a pattern string or a [`Signature`].
+    /// * `data` the bytes to scan
+    /// # Returns
+    /// * [Option<usize>] - The offset into `data` which has been found.
+    fn scan_slice(&self, pattern: impl AsSignature, data: &[u8]) -> Option<usize> {
+        pattern.as_signature()?.find(data)
+    }
+    /// Scans for every match of a pattern in the process, including overlapping ones.
+    /// # Arguments
+    /// * `pattern` - The pattern to scan for, either a pattern string or a [`Signature`].
+    /// * `iter` the iterator to scan
+    /// # Returns
+    /// * an iterator of the addresses which have been found.
+    fn scan_all<'a>(
+        &self,
+        pattern: impl AsSignature,
+        iter: impl Iterator<Item = &'a u8>,
+    ) -> impl Iterator<Item = usize> {
+        let data: Vec<u8> = iter.copied().collect();
+        let found: Vec<usize> = match pattern.as_signature() {
+            Some(signature) => signature.find_all(&data).collect(),
+            None => Vec::new(),
+        };
+        found.into_iter()
+    }
+    /// Scans every region of the process selected by <filter> for a pattern, matches across region boundaries are
+    /// found as well.
+    /// # Arguments
+    /// * `pattern` - The pattern to scan for, either a pattern string or a [`Signature`].
+    /// * `filter` - which regions to scan, [`RegionFilter::new`] scans every readable region except special mappings.
+    /// # Returns
+    /// * [Option<usize>] - The address which has been found.
+    /// # Example
+    /// ```no_run
+    /// use poggers::sigscan::SigScan;
+    /// use poggers::structures::process::Process;
+    /// use poggers::structures::regions::{RegionFilter, RegionKind};
+    /// let process = Process::find_name("game").unwrap();
+    /// let jit = RegionFilter::new().executable().kind(RegionKind::Anonymous);
+    /// let found = process.scan_regions("48 8B 05 ? ? ? ? C3", jit).unwrap();
+    /// ```
+    fn scan_regions(
+        &self,
+        pattern: impl AsSignature,
+        filter: RegionFilter,
+    ) -> Result<Option<usize>, MemError>
+    where
+        Self: Sized,
+    {
+        let Some(signature) = pattern.as_signature() else {
+            return Ok(None);
+        };
+        let mut chunks = ChunkReader::filtered(self, &filter, signature.len() - 1)?;
+        while let Some((addr, data)) = chunks.next_chunk()? {
+            if let Some(result) = signature.find(data) {
+                return Ok(Some(addr + result));
+            }
+        }
+        Ok(None)
+    }
+    /// Scans every region of the process selected by <filter> for every match of a pattern, see
+    /// [`SigScan::scan_regions`].
+    /// # Returns
+    /// * an iterator of the addresses which have been found, regions are read as the iterator advances. it ends
+    ///   after the first read error which is not an unreadable page.
+    fn scan_regions_all(
+        &self,
+        pattern: impl AsSignature,
+        filter: RegionFilter,
+    ) -> Result<impl Iterator<Item = Result<usize, MemError>> + '_, MemError>
+    where
+        Self: Sized,
+    {
+        let signature = pattern.as_signature().map(Cow::into_owned);
+        let overlap = signature.as_ref().map_or(0, |x| x.len() - 1);
+        Ok(
+            ChunkReader::filtered(self, &filter, overlap)?.find_all(move |addr, data| {
+                match &signature {
+                    Some(signature) => signature
+                        .find_all(data)
+                        .map(|result| addr + result)
+                        .collect(),
+                    None => Vec::new(),
+                }
+            }),
+        )
+    }
+    /// Scans the regions selected by <filter> like [`SigScan::scan_regions`], but splits them across a worker
+    /// thread per core. Worth it for multi-gigabyte processes, for small scans the threads cost more than they save.
+    /// # Returns
+    /// * [Option<usize>] - The lowest address which has been found.
+    fn par_scan_regions(
+        &self,
+        pattern: impl AsSignature,
+        filter: RegionFilter,
+    ) -> Result<Option<usize>, MemError>
+    where
+        Self: Sized + Sync,
+    {
+        let Some(signature) = pattern.as_signature() else {
+            return Ok(None);
+        };
+        let runs = filtered_runs(self, &filter)?;
+        let found = parallel::scan_runs(self, runs, signature.len() - 1, true, |addr, data| {
+            signature.find(data).map(|x| addr + x).into_iter().collect()
+        })?;
+        Ok(found.first().copied())
+    }
+    /// Scans the regions selected by <filter> for every match of a pattern across a worker thread per core, see
+    /// [`SigScan::par_scan_regions`].
+    /// # Returns
+    /// * every address which has been found, in address order.
+    fn par_scan_regions_all(
+        &self,
+        pattern: impl AsSignature,
+        filter: RegionFilter,
+    ) -> Result<Vec<usize>, MemError>
+    where
+        Self: Sized + Sync,
+    {
+        let Some(signature) = pattern.as_signature() else {
+            return Ok(Vec::new());
+        };
+        let runs = filtered_runs(self, &filter)?;
+        parallel::scan_runs(self, runs, signature.len() - 1, false, |addr, data| {
+            signature.find_all(data).map(|x| addr + x).collect()
+        })
+    }
+    /// Scans the regions selected by <filter> for every signature in <set> at once, reading every page only once.
+    /// # Returns
+    /// * [SetMatches] - where each signature was found, use [`SetMatches::missing`] and [`SetMatches::ambiguous`]
+    ///   to find the signatures which did not match exactly once.
+    fn scan_set(&self, set: &SignatureSet, filter: RegionFilter) -> Result<SetMatches, MemError>
+    where
+        Self: Sized,
+    {
+        set.scan(self, &filter)
+    }
+    /// Lists the printable strings in the regions selected by <filter>, like the `strings` tool. Both single byte
+    /// (ASCII and UTF-8) and UTF-16 little endian strings are found, the latter only at even addresses.
+    /// # Arguments
+    /// * `filter` - which regions to scan, [`RegionFilter::new`] scans every readable region except special mappings.
+    /// * `min_len` - the minimum number of characters of a string, 0 is treated as 1.
+    /// # Returns
+    /// * an iterator of the [FoundString]s in address order, regions are read as the iterator advances. it ends
+    ///   after the first read error which is not an unreadable page.
+    /// # Example
+    /// ```no_run
+    /// use poggers::sigscan::SigScan;
+    /// use poggers::structures::process::Process;
+    /// use poggers::structures::regions::{RegionFilter, RegionKind};
+    /// let process = Process::find_name("game").unwrap();
+    /// let heap = RegionFilter::new().kind(RegionKind::Heap);
+    /// for found in process.strings(heap, 6).unwrap() {
+    ///     let found = found.unwrap();
+    ///     println!("{:X} {}", found.get_address(), found.get_text());
+    /// }
+    /// ```
+    fn strings(
+        &self,
+        filter: RegionFilter,
+        min_len: usize,
+    ) -> Result<impl Iterator<Item = Result<FoundString, MemError>> + '_, MemError>
+    where
+        Self: Sized,
+    {
+        Ok(strings::Strings::new(
+            ChunkReader::filtered(self, &filter, 0)?,
+            min_len,
+        ))
+    }
+    /// scans for a value in a page
+    fn scan_batch_value<T: Sized>(&self, val: &T, page: &[u8]) -> Option<usize> {
+        self.scan_batch_value_all(val, page).next()
+    }
+    /// scans for every instance of a value in a page, only offsets aligned to the size of <T> are checked
+    fn scan_batch_value_all<'p, T: Sized>(
+        &self,
+        val: &T,
+        page: &'p [u8],
+    ) -> impl Iterator<Item = usize> + 'p {
+        let type_size = std::mem::size_of::<T>();
+        let mut val_arr = vec![0; type_size];
+        unsafe {
+            (val as *const T as *const u8).copy_to_nonoverlapping(val_arr.as_mut_ptr(), type_size)
+        };
+        page.chunks(type_size)
+            .enumerate()
+            .filter(move |(_, val)| *val == val_arr)
+            .map(move |(i, _)| i * type_size)
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::SigScan;
+    use crate::structures::{
+        process::Process,
+        regions::{RegionFilter, RegionKind},
+    };
+    use crate::{testing::Mapping, traits::Mem};
+
+    #[test]
+    fn test_scan_regions() {
+        const PATTERN: &str = "5A C3 91 0E 7B ? D4 28";
+        let map = Mapping::new(0x1000);
+        let page = map.start();
+        map.write(0x10, &[0x5A, 0xC3, 0x91, 0x0E, 0x7B, 0x66, 0xD4, 0x28]);
+        let ex = map.process();
+        let anon = RegionFilter::new().writable().kind(RegionKind::Anonymous);
+        let mut found = ex.scan_regions_all(PATTERN, anon.clone()).unwrap();
+        assert!(found.any(|x| x.unwrap() == page + 0x10));
+
+        let range = anon.clone().range(page..page + 0x1000);
+        assert_eq!(ex.scan_regions(PATTERN, range).unwrap(), Some(page + 0x10));
+        let range = anon.range(page + 0x11..page + 0x1000);
+        assert_eq!(ex.scan_regions(PATTERN, range).unwrap(), None);
+
+        let filters = [
+            RegionFilter::new().executable().range(page..page + 0x1000),
+            RegionFilter::new()
+                .kind(RegionKind::File)
+                .range(page..page + 0x1000),
+            RegionFilter::new()
+                .module("libc.so.6")
+                .range(page..page + 0x1000),
+        ];
+        for filter in filters {
+            assert_eq!(ex.scan_regions(PATTERN, filter).unwrap(), None);
+        }
+    }
+    #[test]
+    fn test_scan_regions_module() {
+        let this = Process::this_process();
+        let exe = std::env::current_exe().unwrap();
+        let name = exe.file_name().unwrap().to_str().unwrap();
+        // the ELF magic at the start of the executable
+        let found = this
+            .scan_regions("7F 45 4C 46", RegionFilter::new().module(name))
+            .unwrap()
+            .unwrap();
+        assert_eq!(this.query(found).unwrap().get_path(), Some(exe.as_path()));
+    }
+    #[test]
+    fn test_scan_regions_internal() {
+        const PATTERN: &str = "3E 91 C7 ? 0D 58";
+        let this = Process::this_process();
+        // reads every selected region, including the buffer the scan reads into
+        for filter in [RegionFilter::new(), RegionFilter::new().writable()] {
+            let found = this
+                .scan_regions_all(PATTERN, filter)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>();
+            assert!(found.is_ok());
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use std::{
+    ops::Range,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use super::chunks::{ChunkReader, CHUNK_SIZE};
+use crate::traits::{Mem, MemError};
+
+/// how much memory a worker scans before taking the next piece
+const PIECE_SIZE: usize = CHUNK_SIZE * 4;
+
+/// a part of a run which is scanned by a single worker
+struct Piece {
+    /// matches starting in this range belong to the piece
+    range: Range<usize>,
+    /// where reading stops, past the end of <range> so matches straddling the next piece are found
+    read_end: usize,
+}
+
+/// splits <runs> into pieces of at most [`PIECE_SIZE`], in address order
+fn split_runs(runs: Vec<Range<usize>>, overlap: usize) -> Vec<Piece> {
+    let mut pieces = Vec::new();
+    for run in runs {
+        let mut start = run.start;
+        while start < run.end {
+            let end = run.end.min(start.saturating_add(PIECE_SIZE));
+            pieces.push(Piece {
+                range: start..end,
+                read_end: run.end.min(end.saturating_add(overlap)),
+            });
+            start = end;
+        }
+    }
+    pieces
+}
+
+/// scans <runs> of <mem> across worker threads, calling <find> with the address of every chunk and its bytes.
+/// the results are returned in address order, if <first_only> is set only the first result is returned.
+/// pages which fault are skipped, any other read error stops the scan and is returned.
+pub(crate) fn scan_runs<M, F>(
+    mem: &M,
+    runs: Vec<Range<usize>>,
+    overlap: usize,
+    first_only: bool,
+    find: F,
+) -> Result<Vec<usize>, MemError>
+where
+    M: Mem + Sync,
+    F: Fn(usize, &[u8]) -> Vec<usize> + Sync,
+{
+    let pieces = split_runs(runs, overlap);
+    let workers = std::thread::available_parallelism()
+        .map_or(1, |x| x.get())
+        .min(pieces.len());
+    let next = AtomicUsize::new(0);
+    // the first piece which had a match, later pieces do not need to be scanned when only the first is wanted
+    let first = AtomicUsize::new(usize::MAX);
+
+    let work = || {
+        let mut results = Vec::new();
+        loop {
+            let index = next.fetch_add(1, Ordering::Relaxed);
+            if index >= pieces.len() || (first_only && index > first.load(Ordering::Relaxed)) {
+                break;
+            }
+            let piece = &pieces[index];
+            let mut reader = ChunkReader::new(
+                mem,
+                std::iter::once(piece.range.start..piece.read_end).collect(),
+                overlap,
+            );
+            let mut found = Vec::new();
+            while let Some((addr, data)) = reader.next_chunk()? {
+                found.extend(
+                    find(addr, data)
+                        .into_iter()
+                        .filter(|x| piece.range.contains(x)),
+                );
+                if first_only && !found.is_empty() {
+                    first.fetch_min(index, Ordering::Relaxed);
+                    found.truncate(1);
+                    break;
+                }
+            }
+            if !found.is_empty() {
+                results.push((index, found));
+            }
+        }
+        Ok::<_, MemError>(results)
+    };
+    let results = std::thread::scope(|scope| {
+        let handles = (0..workers).map(|_| scope.spawn(work)).collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+    let mut results: Vec<(usize, Vec<usize>)> = results.into_iter().flatten().collect();
+    results.sort_unstable_by_key(|(index, _)| *index);
+    let results = results.into_iter().flat_map(|(_, found)| found);
+    if first_only {
+        Ok(results.take(1).collect())
+    } else {
+        Ok(results.collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split_runs, PIECE_SIZE};
+
+    #[test]
+    fn test_split_runs() {
+        let pieces = split_runs(vec![0..PIECE_SIZE * 2 + 0x10, 0x1000_0000..0x1000_1000], 7);
+        let ranges = pieces
+            .iter()
+            .map(|x| (x.range.clone(), x.read_end))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ranges,
+            [
+                (0..PIECE_SIZE, PIECE_SIZE + 7),
+                (PIECE_SIZE..PIECE_SIZE * 2, PIECE_SIZE * 2 + 7),
+                (PIECE_SIZE * 2..PIECE_SIZE * 2 + 0x10, PIECE_SIZE * 2 + 0x10),
+                (0x1000_0000..0x1000_1000, 0x1000_1000),
+            ]
+        );
+    }
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_par_scan_piece_boundaries() {
+        use crate::{sigscan::SigScan, structures::regions::RegionFilter, testing::Mapping};
+
+        const PATTERN: &str = "E1 7C ? 3B 9F 02";
+        let needle = [0xE1u8, 0x7C, 0x00, 0x3B, 0x9F, 0x02];
+        let size = PIECE_SIZE * 3;
+        let at = [5, PIECE_SIZE - 3, PIECE_SIZE * 2 - 1, size - needle.len()];
+        let map = Mapping::new(size);
+        for offset in at {
+            map.write(offset, &needle);
+        }
+        let page = map.start();
+        let ex = map.process();
+        let filter = map.filter();
+        let expected = at.map(|x| page + x);
+
+        let found = ex.par_scan_regions_all(PATTERN, filter.clone()).unwrap();
+        assert_eq!(found, expected);
+        let sequential = ex
+            .scan_regions_all(PATTERN, filter.clone())
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(found, sequential);
+        assert_eq!(
+            ex.par_scan_regions(PATTERN, filter).unwrap(),
+            Some(expected[0])
+        );
+
+        let filter = RegionFilter::new().range(page + 6..page + size);
+        assert_eq!(
+            ex.par_scan_regions(PATTERN, filter).unwrap(),
+            Some(expected[1])
+        );
+    }
+}
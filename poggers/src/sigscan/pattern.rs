@@ -0,0 +1,91 @@
+use std::borrow::Cow;
+
+use poggers_pattern::ByteSet;
+
+use super::{AsSignature, Signature};
+
+/// A pattern which was parsed at compile time by `sig!` from `poggers-derive`, as its bytes and a mask of the bits
+/// in every byte which have to match. scanning for it does not parse anything.
+/// ```ignore
+/// use poggers::sigscan::Pattern;
+/// use poggers_derive::sig;
+/// // a malformed pattern is a compile error
+/// const WORLD: Pattern = sig!("48 8B 05 ? ? ? ? 4? 85 C0");
+/// let found = module.scan(WORLD)?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pattern {
+    bytes: &'static [u8],
+    mask: &'static [u8],
+}
+
+impl Pattern {
+    /// create a pattern from <bytes> and the bits of them which have to match in <mask>, a mask of `0` is a wildcard
+    /// # Panics
+    /// when <bytes> is empty or <mask> does not have the same length, which is a compile error in a const
+    pub const fn new(bytes: &'static [u8], mask: &'static [u8]) -> Self {
+        assert!(!bytes.is_empty(), "a pattern cannot be empty");
+        assert!(
+            bytes.len() == mask.len(),
+            "a pattern needs a mask for every byte"
+        );
+        Self { bytes, mask }
+    }
+    /// Get the bytes of the pattern
+    pub const fn get_bytes(&self) -> &'static [u8] {
+        self.bytes
+    }
+    /// Get the mask of the bits which have to match
+    pub const fn get_mask(&self) -> &'static [u8] {
+        self.mask
+    }
+    /// the length of the pattern in bytes
+    pub const fn len(&self) -> usize {
+        self.bytes.len()
+    }
+    /// always false, a pattern cannot be empty
+    pub const fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+}
+
+impl From<Pattern> for Signature {
+    fn from(pattern: Pattern) -> Self {
+        let bytes = pattern
+            .bytes
+            .iter()
+            .zip(pattern.mask)
+            .map(|(value, mask)| ByteSet::masked(*value, *mask))
+            .collect();
+        Signature::from_bytes(bytes)
+    }
+}
+
+impl AsSignature for Pattern {
+    fn as_signature(&self) -> Option<Cow<'_, Signature>> {
+        Some(Cow::Owned((*self).into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pattern;
+    use crate::sigscan::{AsSignature, Signature};
+
+    const LOAD: Pattern = poggers_derive::sig!("[48|4C] 8B 0? ?{2} ?5 C3");
+
+    #[test]
+    fn test_sig_macro() {
+        assert_eq!(LOAD.len(), 7);
+        assert_eq!(LOAD.get_bytes(), [0x48, 0x8B, 0x00, 0, 0, 0x05, 0xC3]);
+        assert_eq!(LOAD.get_mask(), [0xFB, 0xFF, 0xF0, 0, 0, 0x0F, 0xFF]);
+        let signature = Signature::from(LOAD);
+        assert_eq!(
+            signature,
+            Signature::from_ida("[48|4C] 8B 0? ? ? ?5 C3").unwrap()
+        );
+        assert_eq!(signature.to_string(), "[48|4C] 8B 0? ? ? ?5 C3");
+        let data = [0x90, 0x4C, 0x8B, 0x0D, 1, 2, 0x45, 0xC3];
+        assert_eq!(LOAD.as_signature().unwrap().find(&data), Some(1));
+    }
+}
@@ -0,0 +1,497 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    ops::Range,
+    path::Path,
+    str::FromStr,
+};
+
+use super::chunks::{readable_runs, ChunkReader};
+use crate::{
+    structures::{
+        addr::PointerPath,
+        regions::{module_name_eq, RegionFilter, RegionKind},
+    },
+    traits::{Mem, MemError},
+};
+
+/// Errors which can occur during a pointer scan
+#[derive(Debug, thiserror::Error)]
+pub enum PointerScanError {
+    /// reading the process failed
+    #[error(transparent)]
+    Mem(#[from] MemError),
+    /// saving or loading the results failed
+    #[error("unable to save or load results: {0}")]
+    Io(#[from] std::io::Error),
+    /// a saved pointer chain could not be parsed
+    #[error("invalid pointer chain '{0}'")]
+    InvalidChain(String),
+    /// the module a pointer chain starts from is not loaded
+    #[error("module {0} is not loaded")]
+    ModuleNotFound(String),
+}
+
+/// a chain of pointers from a static address in a module to a target, `module+offset -> o1 -> o2 ...`.
+/// the pointer at `module+offset` is read, `o1` is added to it and the pointer there is read, and so on.
+/// the last offset is added without reading, giving the target.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PointerChain {
+    pub(crate) module: String,
+    pub(crate) offset: usize,
+    pub(crate) offsets: Vec<usize>,
+}
+
+impl PointerChain {
+    /// create a chain starting at <offset> into <module>, e.g. `game.exe`
+    pub fn new(module: impl Into<String>, offset: usize, offsets: Vec<usize>) -> Self {
+        Self {
+            module: module.into(),
+            offset,
+            offsets,
+        }
+    }
+    /// Get the file name of the module the chain starts in
+    pub fn get_module(&self) -> &str {
+        &self.module
+    }
+    /// Get the offset of the first pointer from the start of the module
+    pub const fn get_offset(&self) -> usize {
+        self.offset
+    }
+    /// Get the offsets added at every level of the chain
+    pub fn get_offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+    /// Get the offsets as a [`PointerPath`], which is followed from the module base plus [`PointerChain::get_offset`]
+    pub fn get_path(&self) -> PointerPath {
+        PointerPath::new(self.offsets.as_slice())
+    }
+    /// follow the chain in <mem>, returns the address it ends at
+    pub fn resolve<M: Mem>(&self, mem: &M) -> Result<usize, PointerScanError> {
+        let files = mapped_files(mem)?;
+        let base = module_base(&files, &self.module)
+            .ok_or_else(|| PointerScanError::ModuleNotFound(self.module.clone()))?;
+        Ok(self.resolve_from(mem, base)?)
+    }
+    /// follow the chain with the module loaded at <base>
+    fn resolve_from<M: Mem>(&self, mem: &M, base: usize) -> Result<usize, MemError> {
+        unsafe { self.get_path().resolve(mem, base + self.offset) }
+    }
+}
+
+impl Display for PointerChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}+{:X}", self.module, self.offset)?;
+        for offset in &self.offsets {
+            write!(f, " -> {offset:X}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PointerChain {
+    type Err = PointerScanError;
+    /// parse a chain in the format it is displayed in, `game.exe+1A2B30 -> 10 -> 8`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || PointerScanError::InvalidChain(s.to_string());
+        let mut parts = s.split(" -> ");
+        let (module, offset) = parts
+            .next()
+            .and_then(|x| x.rsplit_once('+'))
+            .ok_or_else(invalid)?;
+        let hex = |x: &str| usize::from_str_radix(x.trim(), 16).map_err(|_| invalid());
+        let offsets = parts.map(hex).collect::<Result<Vec<_>, _>>()?;
+        if module.is_empty() || offsets.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self::new(module, hex(offset)?, offsets))
+    }
+}
+
+/// the pointer chains found by a [`PointerScanner`], shortest chains first
+#[derive(Debug, Clone, Default)]
+pub struct PointerScanResults {
+    chains: Vec<PointerChain>,
+}
+
+impl PointerScanResults {
+    /// the amount of chains
+    pub fn len(&self) -> usize {
+        self.chains.len()
+    }
+    /// are there no chains
+    pub fn is_empty(&self) -> bool {
+        self.chains.is_empty()
+    }
+    /// iterate over the chains
+    pub fn iter(&self) -> impl Iterator<Item = &PointerChain> {
+        self.chains.iter()
+    }
+    /// save the chains to <path>, one per line
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), PointerScanError> {
+        let mut file = BufWriter::new(File::create(path)?);
+        for chain in &self.chains {
+            writeln!(file, "{chain}")?;
+        }
+        file.flush()?;
+        Ok(())
+    }
+    /// load chains saved by [`PointerScanResults::save`] from <path>
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PointerScanError> {
+        let mut chains = Vec::new();
+        for line in BufReader::new(File::open(path)?).lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                chains.push(line.parse()?);
+            }
+        }
+        Ok(Self { chains })
+    }
+    /// keep only the chains which still lead to <target> in <mem>, e.g. after restarting the game and finding the
+    /// value again. returns the amount of chains left.
+    pub fn rescan<M: Mem>(&mut self, mem: &M, target: usize) -> Result<usize, PointerScanError> {
+        let files = mapped_files(mem)?;
+        let mut bases = HashMap::new();
+        self.chains.retain(|chain| {
+            let base = *bases
+                .entry(chain.module.clone())
+                .or_insert_with(|| module_base(&files, &chain.module));
+            base.is_some_and(|base| chain.resolve_from(mem, base).ok() == Some(target))
+        });
+        Ok(self.chains.len())
+    }
+}
+
+impl FromIterator<PointerChain> for PointerScanResults {
+    fn from_iter<I: IntoIterator<Item = PointerChain>>(iter: I) -> Self {
+        Self {
+            chains: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// a region mapped from a file, pointers stored in it are at a static offset from the start of the file
+struct MappedFile {
+    range: Range<usize>,
+    name: String,
+    base: usize,
+}
+
+/// the regions of <mem> mapped from files, in address order
+fn mapped_files<M: Mem>(mem: &M) -> Result<Vec<MappedFile>, MemError> {
+    let mut files: Vec<MappedFile> = Vec::new();
+    let mut bases = HashMap::new();
+    for region in mem.regions()? {
+        let file = region
+            .get_path()
+            .filter(|_| region.get_kind() == RegionKind::File)
+            .and_then(|path| Some((path, path.file_name()?.to_str()?)));
+        let Some((path, name)) = file else {
+            // the zeroed data (.bss) of a module on linux is anonymous memory right after its file mapping
+            if let Some(last) = files.last_mut() {
+                if region.get_kind() == RegionKind::Anonymous && last.range.end == region.start {
+                    last.range.end = region.end;
+                }
+            }
+            continue;
+        };
+        let base = *bases.entry(path.to_path_buf()).or_insert(region.start);
+        files.push(MappedFile {
+            range: region.start..region.end,
+            name: name.to_string(),
+            base,
+        });
+    }
+    Ok(files)
+}
+
+/// where the module named <name> starts
+fn module_base(files: &[MappedFile], name: &str) -> Option<usize> {
+    files
+        .iter()
+        .find(|file| module_name_eq(&file.name, name))
+        .map(|file| file.base)
+}
+
+/// A Cheat Engine style pointer scanner, finds chains of pointers from static addresses in modules to a target
+/// address, which keep working after the game is restarted.
+/// ```no_run
+/// use poggers::sigscan::{PointerScanResults, PointerScanner};
+/// use poggers::structures::process::Process;
+/// let process = Process::find_name("game").unwrap();
+/// let health = 0x1F2E3D4C50;
+/// let results = PointerScanner::new(&process).max_depth(4).scan(health).unwrap();
+/// results.save("health.ptr").unwrap();
+///
+/// // after restarting the game and finding health again
+/// let mut results = PointerScanResults::load("health.ptr").unwrap();
+/// results.rescan(&process, 0x2A3B4C5D60).unwrap();
+/// for chain in results.iter() {
+///     println!("{chain}");
+/// }
+/// ```
+pub struct PointerScanner<'a, M: Mem> {
+    mem: &'a M,
+    filter: RegionFilter,
+    alignment: usize,
+    max_depth: usize,
+    max_offset: usize,
+    max_results: usize,
+}
+
+impl<'a, M: Mem> PointerScanner<'a, M> {
+    /// create a scanner which looks for pointers of the [`Mem::pointer_width`] of <mem> in its writable regions
+    pub fn new(mem: &'a M) -> Self {
+        Self {
+            mem,
+            filter: RegionFilter::new().writable(),
+            alignment: mem.pointer_width(),
+            max_depth: 5,
+            max_offset: 0x1000,
+            max_results: 100_000,
+        }
+    }
+    /// look for pointers in the regions selected by <filter> instead of every writable region
+    pub fn filter(mut self, filter: RegionFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+    /// only look for pointers at addresses which are a multiple of <alignment>, the size of a pointer by default
+    pub fn alignment(mut self, alignment: usize) -> Self {
+        self.alignment = alignment.max(1);
+        self
+    }
+    /// the most pointers in a chain, 5 by default. every level multiplies how long the scan takes
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+    /// the largest offset from a pointer to the next level, 0x1000 by default
+    pub fn max_offset(mut self, offset: usize) -> Self {
+        self.max_offset = offset;
+        self
+    }
+    /// stop after finding <results> chains, 100000 by default
+    pub fn max_results(mut self, results: usize) -> Self {
+        self.max_results = results;
+        self
+    }
+    /// find the chains which lead to <target>
+    pub fn scan(&self, target: usize) -> Result<PointerScanResults, PointerScanError> {
+        let search = Search {
+            pointers: self.pointers()?,
+            files: mapped_files(self.mem)?,
+            scanner: self,
+        };
+        let mut chains = Vec::new();
+        search.search(target, &mut Vec::new(), &mut chains, &mut HashMap::new());
+        chains.sort_by_key(|chain: &PointerChain| chain.offsets.len());
+        Ok(PointerScanResults { chains })
+    }
+    /// every pointer into readable memory, as the address it points to and the address it is stored at, sorted
+    fn pointers(&self) -> Result<Vec<(usize, usize)>, MemError> {
+        let readable = readable_runs(self.mem.regions()?);
+        let is_readable = |value: usize| {
+            let run = readable.partition_point(|run| run.end <= value);
+            readable.get(run).is_some_and(|run| run.contains(&value))
+        };
+        let width = self.mem.pointer_width();
+        let mut pointers = Vec::new();
+        let mut chunks = ChunkReader::filtered(self.mem, &self.filter, 0)?;
+        while let Some((addr, data)) = chunks.next_chunk()? {
+            let mut i = addr.next_multiple_of(self.alignment) - addr;
+            while i + width <= data.len() {
+                let value = match width {
+                    4 => u32::from_ne_bytes(data[i..i + 4].try_into().unwrap()) as usize,
+                    _ => usize::from_ne_bytes(data[i..i + width].try_into().unwrap()),
+                };
+                if is_readable(value) {
+                    pointers.push((value, addr + i));
+                }
+                i += self.alignment;
+            }
+        }
+        pointers.sort_unstable();
+        Ok(pointers)
+    }
+}
+
+/// the state of a running scan
+struct Search<'s, 'a, M: Mem> {
+    pointers: Vec<(usize, usize)>,
+    files: Vec<MappedFile>,
+    scanner: &'s PointerScanner<'a, M>,
+}
+
+impl<M: Mem> Search<'_, '_, M> {
+    /// the mapped file containing <addr>
+    fn file_at(&self, addr: usize) -> Option<&MappedFile> {
+        let file = self.files.partition_point(|file| file.range.end <= addr);
+        self.files
+            .get(file)
+            .filter(|file| file.range.contains(&addr))
+    }
+    /// find the pointers to <target> and follow them back until a static one is found.
+    /// <offsets> are the offsets from <target> to the original target, innermost first.
+    /// <dead> maps the addresses which were searched without finding a chain to the most levels which were left,
+    /// so the same dead end reached through another pointer is not searched again.
+    fn search(
+        &self,
+        target: usize,
+        offsets: &mut Vec<usize>,
+        chains: &mut Vec<PointerChain>,
+        dead: &mut HashMap<usize, usize>,
+    ) {
+        let remaining = self.scanner.max_depth - offsets.len();
+        if dead.get(&target).is_some_and(|&levels| levels >= remaining) {
+            return;
+        }
+        let found = chains.len();
+        let lowest = target.saturating_sub(self.scanner.max_offset);
+        let start = self.pointers.partition_point(|x| x.0 < lowest);
+        let end = self.pointers.partition_point(|x| x.0 <= target);
+        // closest pointers first, they are the most likely to be the start of the object holding <target>
+        for &(value, location) in self.pointers[start..end].iter().rev() {
+            if chains.len() >= self.scanner.max_results {
+                return;
+            }
+            offsets.push(target - value);
+            if let Some(file) = self.file_at(location) {
+                chains.push(PointerChain::new(
+                    file.name.clone(),
+                    location - file.base,
+                    offsets.iter().rev().copied().collect(),
+                ));
+            } else if offsets.len() < self.scanner.max_depth {
+                self.search(location, offsets, chains, dead);
+            }
+            offsets.pop();
+        }
+        if chains.len() == found {
+            dead.insert(target, remaining);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PointerChain;
+
+    #[test]
+    fn test_chain_format() {
+        let chain = PointerChain::new("game.exe", 0x1A2B30, vec![0x10, 0, 0x8]);
+        assert_eq!(chain.to_string(), "game.exe+1A2B30 -> 10 -> 0 -> 8");
+        assert_eq!(chain.to_string().parse::<PointerChain>().unwrap(), chain);
+        let chain: PointerChain = "lib+game.so+10 -> 20".parse().unwrap();
+        assert_eq!(chain.get_module(), "lib+game.so");
+        for invalid in [
+            "game.exe -> 10",
+            "game.exe+10",
+            "+10 -> 8",
+            "game.exe+10 -> G",
+        ] {
+            assert!(invalid.parse::<PointerChain>().is_err(), "{invalid}");
+        }
+    }
+    #[test]
+    fn test_search_dead_ends() {
+        use super::{MappedFile, PointerScanner, Search};
+        use crate::structures::process::Process;
+        use std::collections::HashMap;
+
+        let this = Process::this_process();
+        let scanner = PointerScanner::new(&this).max_depth(3).max_offset(0x10);
+        let (target, a, b, e) = (0x10_0000, 0x20_0000, 0x30_0000, 0x60_0000);
+        let mut search = Search {
+            // (value, location), sorted
+            pointers: vec![(0x10_0000, a), (0x10_0000, e), (a, b), (b, 0x1008), (e, a)],
+            files: vec![MappedFile {
+                range: 0x1000..0x2000,
+                name: "game".into(),
+                base: 0x1000,
+            }],
+            scanner: &scanner,
+        };
+        // `a` is first reached through `e` with a single level left, where it is a dead end, but with two levels
+        // left it leads to the static pointer
+        let mut chains = Vec::new();
+        search.search(target, &mut Vec::new(), &mut chains, &mut HashMap::new());
+        assert_eq!(chains, [PointerChain::new("game", 8, vec![0, 0, 0])]);
+
+        // every object of a level points to every object of the level below, without memoizing the dead ends this
+        // would take 32^5 searches
+        let (count, levels) = (32usize, 5usize);
+        let object = |level: usize, i: usize| (level << 28) + (i << 16);
+        let mut pointers = Vec::new();
+        for level in 1..=levels {
+            for i in 0..count {
+                for slot in 0..count {
+                    pointers.push((object(level - 1, slot), object(level, i) + slot * 8));
+                }
+            }
+        }
+        pointers.sort_unstable();
+        search.pointers = pointers;
+        let scanner = PointerScanner::new(&this)
+            .max_depth(levels + 1)
+            .max_offset(0x1000);
+        search.scanner = &scanner;
+        let mut chains = Vec::new();
+        search.search(
+            object(0, 0),
+            &mut Vec::new(),
+            &mut chains,
+            &mut HashMap::new(),
+        );
+        assert!(chains.is_empty());
+    }
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_pointer_scan() {
+        use super::{PointerScanResults, PointerScanner};
+        use crate::structures::process::Process;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // non zero so it is stored in the data of the executable
+        static ROOT: AtomicUsize = AtomicUsize::new(1);
+        let exe = std::env::current_exe().unwrap();
+        let name = exe.file_name().unwrap().to_str().unwrap();
+
+        let inner = Box::new([0usize, 1337]);
+        let outer = Box::new([0usize, 0, &*inner as *const _ as usize]);
+        ROOT.store(&*outer as *const _ as usize, Ordering::SeqCst);
+        let target = &inner[1] as *const usize as usize;
+
+        let ex = Process::find_pid(std::process::id()).unwrap();
+        let results = PointerScanner::new(&ex)
+            .max_depth(2)
+            .max_offset(0x100)
+            .scan(target)
+            .unwrap();
+        let chain = results
+            .iter()
+            .find(|chain| chain.get_offsets() == [0x10, 0x8] && chain.get_module() == name)
+            .unwrap_or_else(|| panic!("no chain through ROOT in {results:#?}"));
+        assert_eq!(chain.resolve(&ex).unwrap(), target);
+
+        let path = std::env::temp_dir().join(format!("poggers-ptr-{}.txt", std::process::id()));
+        results.save(&path).unwrap();
+        let mut loaded = PointerScanResults::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.len(), results.len());
+
+        // the objects move, like they would after a restart
+        let moved = Box::new([0usize, 1337]);
+        let new_target = &moved[1] as *const usize as usize;
+        let outer_moved = Box::new([0usize, 0, &*moved as *const _ as usize]);
+        ROOT.store(&*outer_moved as *const _ as usize, Ordering::SeqCst);
+        drop((inner, outer));
+        loaded.rescan(&ex, new_target).unwrap();
+        assert!(loaded.iter().any(|x| x == chain));
+        assert!(loaded.iter().all(|x| x.resolve(&ex).unwrap() == new_target));
+        drop((moved, outer_moved));
+    }
+}
@@ -0,0 +1,209 @@
+use super::{chunks::ChunkReader, Signature, SignatureError};
+use crate::{
+    structures::regions::RegionFilter,
+    traits::{Mem, MemError},
+};
+
+/// A set of named signatures which are all scanned for in a single pass, reading every page only once.
+/// ```no_run
+/// use poggers::sigscan::SignatureSet;
+/// use poggers::structures::process::{implement::utils::ProcessUtils, Process};
+/// let mut set = SignatureSet::new();
+/// set.add("local_player", "48 8B 05 ? ? ? ? 48 85 C0").unwrap();
+/// set.add("entity_list", "4C 8D 0D ? ? ? ? 41 8B D0").unwrap();
+///
+/// let process = Process::find_name("game").unwrap();
+/// let results = process.get_base_module().unwrap().scan_set(&set).unwrap();
+/// for name in results.missing() {
+///     println!("{name} was not found");
+/// }
+/// let local_player = results.get("local_player").unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SignatureSet {
+    entries: Vec<(String, Signature)>,
+}
+
+/// The matches of every signature in a [`SignatureSet`]
+#[derive(Debug, Clone)]
+pub struct SetMatches {
+    matches: Vec<(String, Vec<usize>)>,
+}
+
+/// Why a signature in a [`SetMatches`] did not have a single match
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SetMatchError {
+    /// there is no signature with the name in the set
+    #[error("'{0}' is not in the signature set")]
+    Unknown(String),
+    /// the signature was not found
+    #[error("'{0}' was not found")]
+    Missing(String),
+    /// the signature was found more than once
+    #[error("'{0}' was found {1} times")]
+    Ambiguous(String, usize),
+}
+
+impl SignatureSet {
+    /// create an empty set
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// parse an IDA style <pattern> and add it to the set as <name>
+    pub fn add(&mut self, name: impl Into<String>, pattern: &str) -> Result<(), SignatureError> {
+        self.insert(name, Signature::from_ida(pattern)?);
+        Ok(())
+    }
+    /// add a [`Signature`] to the set as <name>
+    pub fn insert(&mut self, name: impl Into<String>, signature: Signature) {
+        self.entries.push((name.into(), signature));
+    }
+    /// the amount of signatures in the set
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    /// is the set empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    /// iterate over the names and signatures in the set
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Signature)> {
+        self.entries.iter().map(|(name, sig)| (name.as_str(), sig))
+    }
+    /// scans the regions of <mem> selected by <filter> for every signature at once
+    pub(crate) fn scan<M: Mem>(
+        &self,
+        mem: &M,
+        filter: &RegionFilter,
+    ) -> Result<SetMatches, MemError> {
+        let overlap = self.entries.iter().map(|(_, sig)| sig.len() - 1).max();
+        let mut chunks = ChunkReader::filtered(mem, filter, overlap.unwrap_or(0))?;
+        let mut matches: Vec<(String, Vec<usize>)> = self
+            .entries
+            .iter()
+            .map(|(name, _)| (name.clone(), Vec::new()))
+            .collect();
+        while let Some((addr, data)) = chunks.next_chunk()? {
+            for ((_, signature), (_, found)) in self.entries.iter().zip(&mut matches) {
+                for result in signature.find_all(data).map(|x| addr + x) {
+                    // shorter signatures can be found again in the overlap with the previous chunk
+                    if found.last().is_none_or(|last| *last < result) {
+                        found.push(result);
+                    }
+                }
+            }
+        }
+        Ok(SetMatches { matches })
+    }
+}
+
+impl<N: Into<String>> FromIterator<(N, Signature)> for SignatureSet {
+    fn from_iter<I: IntoIterator<Item = (N, Signature)>>(iter: I) -> Self {
+        Self {
+            entries: iter
+                .into_iter()
+                .map(|(name, sig)| (name.into(), sig))
+                .collect(),
+        }
+    }
+}
+
+impl SetMatches {
+    /// get the address of <name>, fails if it was not found exactly once
+    pub fn get(&self, name: &str) -> Result<usize, SetMatchError> {
+        match self.matches(name) {
+            None => Err(SetMatchError::Unknown(name.to_string())),
+            Some([]) => Err(SetMatchError::Missing(name.to_string())),
+            Some([addr]) => Ok(*addr),
+            Some(found) => Err(SetMatchError::Ambiguous(name.to_string(), found.len())),
+        }
+    }
+    /// every address <name> was found at, none if <name> is not in the set
+    pub fn matches(&self, name: &str) -> Option<&[usize]> {
+        self.matches
+            .iter()
+            .find(|(x, _)| x == name)
+            .map(|(_, found)| found.as_slice())
+    }
+    /// iterate over the name of every signature and where it was found, in the order they were added
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[usize])> {
+        self.matches
+            .iter()
+            .map(|(name, found)| (name.as_str(), found.as_slice()))
+    }
+    /// the names of the signatures which were not found
+    pub fn missing(&self) -> impl Iterator<Item = &str> {
+        self.iter()
+            .filter(|(_, found)| found.is_empty())
+            .map(|(name, _)| name)
+    }
+    /// the signatures which were found more than once, along with every address they were found at
+    pub fn ambiguous(&self) -> impl Iterator<Item = (&str, &[usize])> {
+        self.iter().filter(|(_, found)| found.len() > 1)
+    }
+    /// get the address of every signature, or every signature which was not found exactly once
+    pub fn unique(&self) -> Result<Vec<(&str, usize)>, Vec<SetMatchError>> {
+        let (found, errors): (Vec<_>, Vec<_>) = self
+            .iter()
+            .map(|(name, _)| self.get(name).map(|addr| (name, addr)))
+            .partition(Result::is_ok);
+        if errors.is_empty() {
+            Ok(found.into_iter().flatten().collect())
+        } else {
+            Err(errors.into_iter().filter_map(Result::err).collect())
+        }
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::{SetMatchError, SignatureSet};
+    use crate::{
+        sigscan::{chunks::CHUNK_SIZE, SigScan},
+        testing::Mapping,
+    };
+
+    #[test]
+    fn test_scan_set() {
+        let size = CHUNK_SIZE * 2;
+        let map = Mapping::new(size);
+        let page = map.start();
+        // straddles the two chunks
+        map.write(
+            CHUNK_SIZE - 4,
+            &[0xC8, 0x1D, 0x77, 0x30, 0xAE, 0x55, 0x19, 0x6B],
+        );
+        // short enough to lie within the overlap of the two chunks
+        map.write(CHUNK_SIZE - 20, &[0x3E, 0xF4, 0x0B]);
+        map.write(0x100, &[0x91, 0xD2, 0x6A]);
+        map.write(size - 3, &[0x91, 0xD2, 0x6A]);
+        let mut set = SignatureSet::new();
+        set.add("straddling", "C8 1D 77 ? AE 55 19 6B").unwrap();
+        set.add("overlap", "3E F4 0B").unwrap();
+        set.add("twice", "91 D2 6A").unwrap();
+        set.add("missing", "91 D2 6B 04").unwrap();
+        assert!(set.add("invalid", "91 D").is_err());
+
+        let results = map.process().scan_set(&set, map.filter()).unwrap();
+        assert_eq!(results.get("straddling"), Ok(page + CHUNK_SIZE - 4));
+        assert_eq!(results.get("overlap"), Ok(page + CHUNK_SIZE - 20));
+        assert_eq!(
+            results.get("twice"),
+            Err(SetMatchError::Ambiguous("twice".to_string(), 2))
+        );
+        assert_eq!(
+            results.get("missing"),
+            Err(SetMatchError::Missing("missing".to_string()))
+        );
+        assert_eq!(
+            results.get("invalid"),
+            Err(SetMatchError::Unknown("invalid".to_string()))
+        );
+        assert_eq!(results.missing().collect::<Vec<_>>(), ["missing"]);
+        assert_eq!(
+            results.ambiguous().collect::<Vec<_>>(),
+            [("twice", [page + 0x100, page + size - 3].as_slice())]
+        );
+        assert_eq!(results.unique().unwrap_err().len(), 2);
+    }
+}
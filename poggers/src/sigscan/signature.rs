@@ -0,0 +1,293 @@
+use std::{borrow::Cow, fmt::Display, str::FromStr};
+
+use poggers_pattern::{ByteSet, PatternError};
+
+use super::matcher::Matcher;
+
+/// A signature which has been parsed once and can be scanned for any amount of times.
+/// ```
+/// use poggers::sigscan::Signature;
+/// // IDA style
+/// let ida: Signature = "48 8B 05 ? ? ? ? C3".parse().unwrap();
+/// // x64dbg style
+/// let x64dbg = Signature::from_ida("48 8B 05 ?? ?? ?? ?? C3").unwrap();
+/// // code style
+/// let code = Signature::from_code(b"\x48\x8B\x05\x00\x00\x00\x00\xC3", "xxx????x").unwrap();
+/// assert_eq!(ida, x64dbg);
+/// assert_eq!(ida, code);
+/// assert_eq!(ida.to_string(), "48 8B 05 ? ? ? ? C3");
+/// // half byte wildcards, byte sets and skips
+/// let extended: Signature = "[48|4C] 8B 0? ?{4} C3".parse().unwrap();
+/// assert!(extended.find(b"\x4C\x8B\x0D\x10\x20\x30\x40\xC3").is_some());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    pub(crate) matcher: Matcher,
+}
+
+/// Signature parsing failures, each carrying the position in the input where parsing failed
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SignatureError {
+    /// the signature contains no bytes
+    #[error("signature is empty")]
+    Empty,
+    /// a byte was not valid hex
+    #[error("invalid byte '{1}' at {0}")]
+    InvalidByte(usize, String),
+    /// a byte was missing its second hex digit
+    #[error("incomplete byte at {0}")]
+    IncompleteByte(usize),
+    /// a `?{n}` skip did not have a count between 1 and [`Signature::MAX_SKIP`]
+    #[error("invalid skip at {0}, expected ?{{n}} with n from 1 to {max}", max = Signature::MAX_SKIP)]
+    InvalidSkip(usize),
+    /// a `[..|..]` byte set was not closed
+    #[error("unclosed byte set at {0}")]
+    UnclosedSet(usize),
+    /// a [`TextPattern`](super::TextPattern) for ASCII contained a character outside of ASCII
+    #[error("character '{1}' at {0} is not ascii")]
+    NotAscii(usize, char),
+    /// a code style escape was not of the form `\xNN`
+    #[error("invalid escape at {0}, expected \\xNN")]
+    InvalidEscape(usize),
+    /// a code style mask character was neither `x` nor `?`
+    #[error("invalid mask character '{1}' at {0}")]
+    InvalidMask(usize, char),
+    /// a code style mask did not have the same length as its bytes
+    #[error("mask is {1} long but there are {0} bytes")]
+    MaskLength(usize, usize),
+}
+
+impl Signature {
+    /// the longest skip allowed by `?{n}`
+    pub const MAX_SKIP: usize = poggers_pattern::MAX_SKIP;
+    /// the longest signature generated by [`Module::generate_signature`](crate::structures::modules::Module::generate_signature)
+    pub const MAX_GENERATED: usize = 128;
+    /// parse an IDA (`48 8B ? ?`) or x64dbg (`48 8B ?? ??`) style signature.
+    /// tokens with more than one byte (`488B`) are split into bytes. on top of that the following are supported:
+    /// * half byte wildcards, `4?` matches `40` to `4F` and `?B` matches `0B` to `FB`
+    /// * byte sets, `[48|4C]` matches either `48` or `4C`, the bytes in a set may have half byte wildcards
+    /// * skips, `?{4}` is the same as `? ? ? ?`
+    pub fn from_ida(pattern: &str) -> Result<Self, SignatureError> {
+        Ok(Self::from_bytes(poggers_pattern::parse_ida(pattern)?))
+    }
+    /// parse a code style signature, where <bytes> are the bytes and <mask> has an `x` for every byte which has to
+    /// match and a `?` for every wildcard.
+    pub fn from_code(bytes: &[u8], mask: &str) -> Result<Self, SignatureError> {
+        if bytes.len() != mask.chars().count() {
+            return Err(SignatureError::MaskLength(
+                bytes.len(),
+                mask.chars().count(),
+            ));
+        }
+        let bytes = bytes
+            .iter()
+            .zip(mask.char_indices())
+            .map(|(byte, (pos, c))| match c {
+                'x' => Ok(ByteSet::exact(*byte)),
+                '?' => Ok(ByteSet::ANY),
+                c => Err(SignatureError::InvalidMask(pos, c)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(bytes)
+    }
+    /// parse a code style signature where the bytes are written out as escapes (`\x48\x8B\x05`), as they are
+    /// when copied from C/C++ source.
+    pub fn from_code_str(pattern: &str, mask: &str) -> Result<Self, SignatureError> {
+        let mut bytes = Vec::with_capacity(pattern.len() / 4);
+        let mut pos = 0;
+        while pos < pattern.len() {
+            if !pattern[pos..].starts_with("\\x") {
+                return Err(SignatureError::InvalidEscape(pos));
+            }
+            if pattern.len() < pos + 4 {
+                return Err(SignatureError::IncompleteByte(pos + 2));
+            }
+            bytes.push(poggers_pattern::parse_hex(pattern, pos + 2)?);
+            pos += 4;
+        }
+        Self::from_code(&bytes, mask)
+    }
+    /// a signature from bytes which are known not to be empty
+    pub(crate) fn from_bytes(bytes: Vec<ByteSet>) -> Self {
+        Self {
+            matcher: Matcher::new(bytes),
+        }
+    }
+    fn new(bytes: Vec<ByteSet>) -> Result<Self, SignatureError> {
+        if bytes.is_empty() {
+            return Err(SignatureError::Empty);
+        }
+        Ok(Self {
+            matcher: Matcher::new(bytes),
+        })
+    }
+    /// the length of the signature in bytes
+    pub fn len(&self) -> usize {
+        self.matcher.len()
+    }
+    /// always false, a signature cannot be empty
+    pub fn is_empty(&self) -> bool {
+        self.matcher.len() == 0
+    }
+    /// find the offset of the first match of the signature in <data>
+    pub fn find(&self, data: &[u8]) -> Option<usize> {
+        self.matcher.find(data)
+    }
+    /// find the offset of every match of the signature in <data>, including overlapping ones
+    pub fn find_all<'a>(&'a self, data: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        self.matcher.find_all(data)
+    }
+}
+
+impl From<PatternError> for SignatureError {
+    fn from(e: PatternError) -> Self {
+        match e {
+            PatternError::Empty => Self::Empty,
+            PatternError::InvalidByte(pos, byte) => Self::InvalidByte(pos, byte),
+            PatternError::IncompleteByte(pos) => Self::IncompleteByte(pos),
+            PatternError::InvalidSkip(pos) => Self::InvalidSkip(pos),
+            PatternError::UnclosedSet(pos) => Self::UnclosedSet(pos),
+        }
+    }
+}
+
+impl FromStr for Signature {
+    type Err = SignatureError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_ida(s)
+    }
+}
+impl TryFrom<&str> for Signature {
+    type Error = SignatureError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::from_ida(value)
+    }
+}
+impl Display for Signature {
+    /// formats the signature IDA style
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, byte) in self.matcher.bytes().iter().enumerate() {
+            if i != 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Anything which can be scanned for, either a pattern string or a pre-compiled [`Signature`].
+/// pattern strings are parsed on every scan and a malformed pattern is treated as not found,
+/// use [`Signature`] to get a [`SignatureError`] instead.
+pub trait AsSignature {
+    /// get the signature, none if it failed to parse
+    fn as_signature(&self) -> Option<Cow<'_, Signature>>;
+}
+impl AsSignature for Signature {
+    fn as_signature(&self) -> Option<Cow<'_, Signature>> {
+        Some(Cow::Borrowed(self))
+    }
+}
+impl AsSignature for str {
+    fn as_signature(&self) -> Option<Cow<'_, Signature>> {
+        Signature::from_ida(self).ok().map(Cow::Owned)
+    }
+}
+impl AsSignature for String {
+    fn as_signature(&self) -> Option<Cow<'_, Signature>> {
+        self.as_str().as_signature()
+    }
+}
+impl<T: AsSignature + ?Sized> AsSignature for &T {
+    fn as_signature(&self) -> Option<Cow<'_, Signature>> {
+        (**self).as_signature()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Signature, SignatureError};
+
+    #[test]
+    fn test_formats_agree() {
+        let ida = Signature::from_ida("48 8B 05 ? ? ? ? C3").unwrap();
+        assert_eq!(ida, Signature::from_ida("48 8B 05 ?? ?? ?? ?? C3").unwrap());
+        assert_eq!(ida, Signature::from_ida("488B05 ? ? ? ? C3").unwrap());
+        assert_eq!(
+            ida,
+            Signature::from_code(b"\x48\x8B\x05\xAA\xAA\xAA\xAA\xC3", "xxx????x").unwrap()
+        );
+        assert_eq!(
+            ida,
+            Signature::from_code_str(r"\x48\x8B\x05\x00\x00\x00\x00\xC3", "xxx????x").unwrap()
+        );
+        assert_eq!(ida.to_string(), "48 8B 05 ? ? ? ? C3");
+        assert_eq!(ida.len(), 8);
+        assert_eq!(ida, Signature::from_ida("48 8B 05 ?{4} C3").unwrap());
+    }
+    #[test]
+    fn test_extended_syntax() {
+        let sig = Signature::from_ida("[48|4C] 8B 0? ?{2} ?5 [4?|50]").unwrap();
+        assert_eq!(sig.len(), 7);
+        assert_eq!(sig.to_string(), "[48|4C] 8B 0? ? ? ?5 [4?|50]");
+        assert_eq!(sig.to_string().parse::<Signature>().unwrap(), sig);
+        assert_eq!(sig.find(&[0x4C, 0x8B, 0x0D, 1, 2, 0x45, 0x50]), Some(0));
+        assert_eq!(sig.find(&[0x49, 0x8B, 0x0D, 1, 2, 0x45, 0x50]), None);
+        assert_eq!(
+            Signature::from_ida("48 ?{0}"),
+            Err(SignatureError::InvalidSkip(3))
+        );
+        assert_eq!(
+            Signature::from_ida("48 ?{4"),
+            Err(SignatureError::InvalidSkip(3))
+        );
+        assert_eq!(
+            Signature::from_ida("48 [48|4C"),
+            Err(SignatureError::UnclosedSet(3))
+        );
+        assert_eq!(
+            Signature::from_ida("48 [48|4]"),
+            Err(SignatureError::IncompleteByte(7))
+        );
+        assert_eq!(
+            Signature::from_ida("48 [48|4CD]"),
+            Err(SignatureError::InvalidByte(7, "4CD".to_string()))
+        );
+        assert_eq!(
+            Signature::from_ida("48 4G"),
+            Err(SignatureError::InvalidByte(3, "4G".to_string()))
+        );
+    }
+    #[test]
+    fn test_errors() {
+        assert_eq!(Signature::from_ida("  "), Err(SignatureError::Empty));
+        assert_eq!(
+            Signature::from_ida("48 8B 0G"),
+            Err(SignatureError::InvalidByte(6, "0G".to_string()))
+        );
+        assert_eq!(
+            Signature::from_ida("48 8B0"),
+            Err(SignatureError::IncompleteByte(5))
+        );
+        assert!(matches!(
+            Signature::from_ida("48 é8"),
+            Err(SignatureError::InvalidByte(3, _))
+        ));
+        assert_eq!(
+            Signature::from_code(b"\x48\x8B", "x"),
+            Err(SignatureError::MaskLength(2, 1))
+        );
+        assert_eq!(
+            Signature::from_code(b"\x48\x8B", "xy"),
+            Err(SignatureError::InvalidMask(1, 'y'))
+        );
+        assert_eq!(
+            Signature::from_code_str(r"\x48x8B", "xx"),
+            Err(SignatureError::InvalidEscape(4))
+        );
+        assert_eq!(
+            Signature::from_code_str(r"\x48\x8", "xx"),
+            Err(SignatureError::IncompleteByte(6))
+        );
+    }
+}
@@ -0,0 +1,481 @@
+use std::{borrow::Cow, collections::VecDeque};
+
+use poggers_pattern::ByteSet;
+
+use super::{chunks::ChunkReader, AsSignature, Signature, SignatureError};
+use crate::traits::{Mem, MemError};
+
+/// the longest string the extractor returns in one piece, longer runs are split
+const MAX_STRING: usize = 0x1000;
+
+/// How text is encoded in memory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StringEncoding {
+    /// single bytes, only characters up to `0x7F`
+    Ascii,
+    /// UTF-8
+    Utf8,
+    /// UTF-16 little endian, as used by windows (`wchar_t`)
+    Utf16,
+}
+
+/// A text to scan for, usable anywhere a pattern is.
+/// ```no_run
+/// use poggers::sigscan::{SigScan, TextPattern};
+/// use poggers::structures::{process::Process, regions::RegionFilter};
+/// let process = Process::find_name("game").unwrap();
+/// let text = TextPattern::utf16("Health").ignore_case();
+/// for addr in process.scan_regions_all(text, RegionFilter::new()).unwrap() {
+///     println!("{:X}", addr.unwrap());
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TextPattern<'s> {
+    text: &'s str,
+    encoding: StringEncoding,
+    ignore_case: bool,
+}
+
+impl<'s> TextPattern<'s> {
+    /// scan for <text> as ASCII
+    pub const fn ascii(text: &'s str) -> Self {
+        Self::new(text, StringEncoding::Ascii)
+    }
+    /// scan for <text> as UTF-8
+    pub const fn utf8(text: &'s str) -> Self {
+        Self::new(text, StringEncoding::Utf8)
+    }
+    /// scan for <text> as UTF-16 little endian
+    pub const fn utf16(text: &'s str) -> Self {
+        Self::new(text, StringEncoding::Utf16)
+    }
+    /// scan for <text> encoded as <encoding>
+    pub const fn new(text: &'s str, encoding: StringEncoding) -> Self {
+        Self {
+            text,
+            encoding,
+            ignore_case: false,
+        }
+    }
+    /// match ASCII letters regardless of their case, for UTF-8 and UTF-16 text too.
+    /// case folding is ASCII-only, every other character still has to match exactly, so `é` does not match `É`.
+    /// a signature matches each byte on its own, which can not express case pairs that differ in more than one byte
+    /// or are encoded with a different length.
+    pub const fn ignore_case(mut self) -> Self {
+        self.ignore_case = true;
+        self
+    }
+    /// compile the text to a [`Signature`]
+    pub fn to_signature(&self) -> Result<Signature, SignatureError> {
+        if self.text.is_empty() {
+            return Err(SignatureError::Empty);
+        }
+        let byte = |x: u8| match self.ignore_case && x.is_ascii_alphabetic() {
+            true => {
+                ByteSet::exact(x.to_ascii_lowercase()).union(ByteSet::exact(x.to_ascii_uppercase()))
+            }
+            false => ByteSet::exact(x),
+        };
+        let bytes = match self.encoding {
+            StringEncoding::Ascii => {
+                if let Some((pos, c)) = self.text.char_indices().find(|(_, c)| !c.is_ascii()) {
+                    return Err(SignatureError::NotAscii(pos, c));
+                }
+                self.text.bytes().map(byte).collect()
+            }
+            StringEncoding::Utf8 => self.text.bytes().map(byte).collect(),
+            StringEncoding::Utf16 => self
+                .text
+                .encode_utf16()
+                .flat_map(|x| {
+                    let [low, high] = x.to_le_bytes();
+                    // only the ascii range has cases to ignore
+                    match high {
+                        0 => [byte(low), ByteSet::exact(0)],
+                        _ => [ByteSet::exact(low), ByteSet::exact(high)],
+                    }
+                })
+                .collect(),
+        };
+        Ok(Signature::from_bytes(bytes))
+    }
+}
+
+impl AsSignature for TextPattern<'_> {
+    fn as_signature(&self) -> Option<Cow<'_, Signature>> {
+        self.to_signature().ok().map(Cow::Owned)
+    }
+}
+
+/// A printable string found by [`SigScan::strings`](super::SigScan::strings)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoundString {
+    pub(crate) addr: usize,
+    pub(crate) encoding: StringEncoding,
+    pub(crate) text: String,
+}
+
+impl FoundString {
+    /// Get the address of the string
+    pub const fn get_address(&self) -> usize {
+        self.addr
+    }
+    /// Get how the string is encoded, [`StringEncoding::Ascii`] or [`StringEncoding::Utf8`] for single byte strings
+    /// depending on whether it has any multi byte characters
+    pub const fn get_encoding(&self) -> StringEncoding {
+        self.encoding
+    }
+    /// Get the text of the string
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// the length of the printable character at the start of <data>, zero if it is not printable and none if <data> ends
+/// before the character does
+fn printable_utf8(data: &[u8]) -> Option<usize> {
+    let len = match data[0] {
+        b'\t' | 0x20..=0x7E => return Some(1),
+        0xC2..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF4 => 4,
+        _ => return Some(0),
+    };
+    let bytes = data.get(..len)?;
+    let printable = std::str::from_utf8(bytes)
+        .ok()
+        .and_then(|x| x.chars().next())
+        .is_some_and(|c| !c.is_control());
+    Some(if printable { len } else { 0 })
+}
+
+/// is the UTF-16 code unit printable, only the ASCII and Latin-1 ranges are, to avoid treating random data as text
+fn printable_utf16(unit: u16) -> bool {
+    matches!(unit, 0x09 | 0x20..=0x7E | 0xA0..=0xFF)
+}
+
+/// finds the printable runs of one encoding, carrying an unfinished run over to the next chunk
+struct RunFinder {
+    encoding: StringEncoding,
+    min_len: usize,
+    /// the bytes of the run which reached the end of the previous chunk, and where they start
+    carry: Vec<u8>,
+    carry_addr: usize,
+}
+
+impl RunFinder {
+    fn new(encoding: StringEncoding, min_len: usize) -> Self {
+        Self {
+            encoding,
+            min_len,
+            carry: Vec::new(),
+            carry_addr: 0,
+        }
+    }
+    /// where the run carried over to the next chunk starts, strings found later can not start before it
+    fn pending(&self) -> Option<usize> {
+        (!self.carry.is_empty()).then_some(self.carry_addr)
+    }
+    /// the run carried over from the previous chunk has ended
+    fn flush(&mut self, found: &mut VecDeque<FoundString>) {
+        let carry = std::mem::take(&mut self.carry);
+        self.finish(self.carry_addr, &carry, found);
+    }
+    /// scan the chunk <data> at <addr>
+    fn feed(&mut self, addr: usize, data: &[u8], found: &mut VecDeque<FoundString>) {
+        let (start, data) = if !self.carry.is_empty() && self.carry_addr + self.carry.len() == addr
+        {
+            let mut joined = std::mem::take(&mut self.carry);
+            joined.extend_from_slice(data);
+            (self.carry_addr, Cow::Owned(joined))
+        } else {
+            self.flush(found);
+            (addr, Cow::Borrowed(data))
+        };
+        let step = match self.encoding {
+            StringEncoding::Utf16 => 2,
+            _ => 1,
+        };
+        // UTF-16 strings are aligned to 2 bytes
+        let mut i = start % step;
+        let mut run = i;
+        while i < data.len() {
+            let len = match self.encoding {
+                StringEncoding::Utf16 => match data.get(i..i + 2) {
+                    Some(unit) => match printable_utf16(u16::from_le_bytes([unit[0], unit[1]])) {
+                        true => 2,
+                        false => 0,
+                    },
+                    None => break,
+                },
+                _ => match printable_utf8(&data[i..]) {
+                    Some(len) => len,
+                    None => break,
+                },
+            };
+            if len == 0 {
+                self.finish(start + run, &data[run..i], found);
+                i += step;
+                run = i;
+            } else if i + len - run > MAX_STRING {
+                self.finish(start + run, &data[run..i], found);
+                run = i;
+            } else {
+                i += len;
+            }
+        }
+        // the run reaches the end of the chunk, it may continue in the next one
+        self.carry_addr = start + run;
+        self.carry = data[run.min(data.len())..].to_vec();
+    }
+    /// report the run <data> at <addr> if it is long enough
+    fn finish(&self, addr: usize, data: &[u8], found: &mut VecDeque<FoundString>) {
+        let (text, encoding) = match self.encoding {
+            StringEncoding::Utf16 => {
+                let units = data
+                    .chunks_exact(2)
+                    .map(|x| u16::from_le_bytes([x[0], x[1]]));
+                (
+                    char::decode_utf16(units)
+                        .map(|x| x.unwrap_or(char::REPLACEMENT_CHARACTER))
+                        .collect::<String>(),
+                    StringEncoding::Utf16,
+                )
+            }
+            _ => {
+                // a run only ends in an incomplete character when the memory after it could not be read
+                let valid = match std::str::from_utf8(data) {
+                    Ok(_) => data.len(),
+                    Err(e) => e.valid_up_to(),
+                };
+                let text = String::from_utf8_lossy(&data[..valid]).into_owned();
+                match text.is_ascii() {
+                    true => (text, StringEncoding::Ascii),
+                    false => (text, StringEncoding::Utf8),
+                }
+            }
+        };
+        if text.chars().count() >= self.min_len {
+            found.push_back(FoundString {
+                addr,
+                encoding,
+                text,
+            });
+        }
+    }
+}
+
+/// extracts printable strings from chunks of memory, see [`SigScan::strings`](super::SigScan::strings)
+pub(crate) struct Strings<'a, M: Mem> {
+    chunks: ChunkReader<'a, M>,
+    finders: [RunFinder; 2],
+    found: VecDeque<FoundString>,
+    /// strings which start after a run still carried by a finder, in address order
+    held: Vec<FoundString>,
+    done: bool,
+}
+
+impl<'a, M: Mem> Strings<'a, M> {
+    pub(crate) fn new(chunks: ChunkReader<'a, M>, min_len: usize) -> Self {
+        Self {
+            chunks,
+            finders: [
+                RunFinder::new(StringEncoding::Utf8, min_len.max(1)),
+                RunFinder::new(StringEncoding::Utf16, min_len.max(1)),
+            ],
+            found: VecDeque::new(),
+            held: Vec::new(),
+            done: false,
+        }
+    }
+}
+
+impl<M: Mem> Iterator for Strings<'_, M> {
+    type Item = Result<FoundString, MemError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(found) = self.found.pop_front() {
+                return Some(Ok(found));
+            }
+            if self.done {
+                return None;
+            }
+            match self.chunks.next_chunk() {
+                Ok(Some((addr, data))) => {
+                    for finder in &mut self.finders {
+                        let mut found = VecDeque::new();
+                        finder.feed(addr, data, &mut found);
+                        self.held.extend(found);
+                    }
+                }
+                Ok(None) => {
+                    self.done = true;
+                    for finder in &mut self.finders {
+                        let mut found = VecDeque::new();
+                        finder.flush(&mut found);
+                        self.held.extend(found);
+                    }
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+            // both encodings are found in address order, keep the combined results in order as well.
+            // a run carried into the next chunk is reported later, so anything after its start waits for it
+            self.held.sort_by_key(|x| x.addr);
+            let limit = self
+                .finders
+                .iter()
+                .filter_map(RunFinder::pending)
+                .min()
+                .unwrap_or(usize::MAX);
+            let ready = self.held.partition_point(|x| x.addr < limit);
+            self.found.extend(self.held.drain(..ready));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::{RunFinder, StringEncoding, TextPattern};
+    use crate::sigscan::SignatureError;
+
+    #[test]
+    fn test_text_pattern() {
+        let data = b"xx hElLo H\0e\0L\0l\0O\0 hello";
+        let ascii = TextPattern::ascii("Hello").to_signature().unwrap();
+        assert_eq!(
+            ascii.find_all(data).collect::<Vec<_>>(),
+            Vec::<usize>::new()
+        );
+        let ascii = TextPattern::ascii("Hello")
+            .ignore_case()
+            .to_signature()
+            .unwrap();
+        assert_eq!(ascii.find_all(data).collect::<Vec<_>>(), vec![3, 20]);
+        let wide = TextPattern::utf16("hello")
+            .ignore_case()
+            .to_signature()
+            .unwrap();
+        assert_eq!(wide.find_all(data).collect::<Vec<_>>(), vec![9]);
+        assert_eq!(wide.len(), 10);
+
+        let utf8 = TextPattern::utf8("héllo")
+            .ignore_case()
+            .to_signature()
+            .unwrap();
+        // only ascii letters are folded, `É` is not `é`
+        assert_eq!(utf8.find("HÉLLO héLLO".as_bytes()), Some(7));
+        let wide = TextPattern::utf16("é")
+            .ignore_case()
+            .to_signature()
+            .unwrap();
+        let upper: Vec<u8> = "É".encode_utf16().flat_map(u16::to_le_bytes).collect();
+        assert_eq!(wide.find(&upper), None);
+        assert!(matches!(
+            TextPattern::ascii("héllo").to_signature(),
+            Err(SignatureError::NotAscii(1, 'é'))
+        ));
+        assert!(matches!(
+            TextPattern::utf16("").to_signature(),
+            Err(SignatureError::Empty)
+        ));
+    }
+    #[test]
+    fn test_runs_across_chunks() {
+        let mut data = b"\x01\x02first string\x00\x00".to_vec();
+        data.extend("wide".encode_utf16().flat_map(u16::to_le_bytes));
+        data.extend(b"\x00\x00\xFFsplit \xC3\xA9t\xC3\xA9\x00ab\x00");
+        let strings = |split: usize| {
+            let mut found = VecDeque::new();
+            let mut finders = [
+                RunFinder::new(StringEncoding::Utf8, 4),
+                RunFinder::new(StringEncoding::Utf16, 4),
+            ];
+            for finder in &mut finders {
+                finder.feed(0x1000, &data[..split], &mut found);
+                finder.feed(0x1000 + split, &data[split..], &mut found);
+                finder.flush(&mut found);
+            }
+            let mut found: Vec<_> = found
+                .into_iter()
+                .map(|x| (x.addr - 0x1000, x.encoding, x.text))
+                .collect();
+            found.sort_by_key(|x| x.0);
+            found
+        };
+        let expected = vec![
+            (2, StringEncoding::Ascii, "first string".to_string()),
+            (16, StringEncoding::Utf16, "wide".to_string()),
+            (27, StringEncoding::Utf8, "split été".to_string()),
+        ];
+        // split in the middle of every string, UTF-16 unit and UTF-8 character
+        for split in 1..data.len() {
+            assert_eq!(strings(split), expected, "split at {split}");
+        }
+    }
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_strings() {
+        use crate::{sigscan::SigScan, testing::Mapping};
+
+        let map = Mapping::new(0x2000);
+        let page = map.start();
+        // across the two pages, which are read as one run
+        map.write(0x1000 - 4, b"poggers string");
+        let ex = map.process();
+        let range = map.filter();
+        let found: Vec<_> = ex
+            .strings(range.clone(), 4)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].get_address(), page + 0x1000 - 4);
+        assert_eq!(found[0].get_text(), "poggers string");
+        assert_eq!(found[0].get_encoding(), StringEncoding::Ascii);
+        assert_eq!(ex.strings(range.clone(), 15).unwrap().count(), 0);
+
+        let pattern = TextPattern::ascii("POGGERS").ignore_case();
+        assert_eq!(
+            ex.scan_regions(pattern, range).unwrap(),
+            Some(page + 0x1000 - 4)
+        );
+    }
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_strings_across_chunks_in_order() {
+        use crate::{
+            sigscan::{chunks::CHUNK_SIZE, SigScan},
+            testing::Mapping,
+        };
+
+        let map = Mapping::new(CHUNK_SIZE + 0x1000);
+        let page = map.start();
+        // carried into the second chunk, every other byte of it is a single character UTF-8 string
+        let wide: Vec<u8> = "straddling"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        map.write(CHUNK_SIZE - 8, &wide);
+        let ex = map.process();
+        let found: Vec<_> = ex
+            .strings(map.filter(), 1)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert!(found.windows(2).all(|x| x[0].addr <= x[1].addr));
+        assert_eq!(found.len(), 11);
+        let wide = found
+            .iter()
+            .find(|x| x.get_encoding() == StringEncoding::Utf16)
+            .unwrap();
+        assert_eq!(wide.get_address(), page + CHUNK_SIZE - 8);
+        assert_eq!(wide.get_text(), "straddling");
+        // no string is empty
+        assert_eq!(ex.strings(map.filter(), 0).unwrap().count(), found.len());
+    }
+}
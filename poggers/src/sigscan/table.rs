@@ -0,0 +1,186 @@
+use super::{SetMatchError, SigScan, SignatureError, SignatureSet};
+use crate::{
+    structures::{addr::Address, modules::Module},
+    traits::MemError,
+};
+
+/// Why a signature of a [`SignatureTable`] could not be resolved
+#[derive(Debug, thiserror::Error)]
+pub enum SignatureFailure {
+    /// the pattern could not be parsed
+    #[error("'{0}' is invalid: {1}")]
+    Invalid(String, SignatureError),
+    /// the signature was not found exactly once
+    #[error(transparent)]
+    Match(#[from] SetMatchError),
+    /// reading the RIP relative displacement failed
+    #[error("'{0}' could not be resolved: {1}")]
+    Mem(String, MemError),
+}
+
+/// Errors which can occur while resolving a [`SignatureTable`]
+#[derive(Debug, thiserror::Error)]
+pub enum SignatureTableError {
+    /// the module could not be scanned
+    #[error("unable to scan the module: {0}")]
+    Scan(#[from] MemError),
+    /// every signature which could not be resolved
+    #[error("{} signatures failed: {}", .0.len(), .0.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+    Failed(Vec<SignatureFailure>),
+}
+
+/// how the match of a signature is turned into the address which is wanted
+#[derive(Debug, Clone)]
+struct TableEntry {
+    name: String,
+    offset: isize,
+    rip: Option<(usize, usize)>,
+}
+
+/// Named signatures which are resolved to addresses in a single pass over a module, reporting every signature which
+/// failed at once. this is what `#[signature_table]` from `poggers-derive` expands to.
+/// ```no_run
+/// use poggers::sigscan::SignatureTable;
+/// use poggers::structures::process::{implement::utils::ProcessUtils, Process};
+/// let mut table = SignatureTable::new();
+/// // `mov rax, [rip + disp]`, resolved to the global it reads
+/// table.add("world", "48 8B 05 ? ? ? ? 48 85 C0", 0, Some((3, 7)));
+/// table.add("update", "40 53 48 83 EC 20 8B D9", 0, None);
+///
+/// let process = Process::find_name("game").unwrap();
+/// let [world, update] = table.resolve(&process.get_base_module().unwrap()).unwrap()[..] else {
+///     unreachable!()
+/// };
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SignatureTable {
+    set: SignatureSet,
+    entries: Vec<TableEntry>,
+    invalid: Vec<(String, SignatureError)>,
+}
+
+impl SignatureTable {
+    /// create an empty table
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// add the signature <name>. <offset> is added to the match, and when <rip> is some the address is then resolved
+    /// as a RIP relative operand with the offset of the displacement and the length of the instruction, see
+    /// [`Address::rip_relative`]. invalid patterns are reported by [`SignatureTable::resolve`].
+    pub fn add(
+        &mut self,
+        name: impl Into<String>,
+        pattern: &str,
+        offset: isize,
+        rip: Option<(usize, usize)>,
+    ) {
+        let name = name.into();
+        if let Err(e) = self.set.add(name.clone(), pattern) {
+            self.invalid.push((name.clone(), e));
+        }
+        self.entries.push(TableEntry { name, offset, rip });
+    }
+    /// the amount of signatures in the table
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    /// is the table empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    /// scan <module> for every signature, returns the addresses in the order the signatures were added
+    pub fn resolve<T: SigScan>(
+        &self,
+        module: &Module<T>,
+    ) -> Result<Vec<usize>, SignatureTableError> {
+        let matches = module.scan_set(&self.set)?;
+        let mut failures: Vec<SignatureFailure> = Vec::new();
+        let mut found = Vec::with_capacity(self.entries.len());
+        for entry in &self.entries {
+            if let Some((_, e)) = self.invalid.iter().find(|(name, _)| *name == entry.name) {
+                failures.push(SignatureFailure::Invalid(entry.name.clone(), e.clone()));
+                continue;
+            }
+            let addr = match matches.get(&entry.name) {
+                Ok(addr) => addr.wrapping_add_signed(entry.offset),
+                Err(e) => {
+                    failures.push(e.into());
+                    continue;
+                }
+            };
+            let Some((disp_off, insn_len)) = entry.rip else {
+                found.push(addr);
+                continue;
+            };
+            let address = Address::new(module.get_owner(), addr);
+            match unsafe { address.rip_relative(disp_off, insn_len) } {
+                Ok(resolved) => found.push(resolved.get_address()),
+                Err(e) => failures.push(SignatureFailure::Mem(entry.name.clone(), e)),
+            }
+        }
+        if failures.is_empty() {
+            Ok(found)
+        } else {
+            Err(SignatureTableError::Failed(failures))
+        }
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::{SignatureFailure, SignatureTable, SignatureTableError};
+    use crate::{sigscan::SetMatchError, testing::Mapping};
+
+    #[poggers_derive::signature_table]
+    struct Offsets {
+        #[sig("C4 6B 91 2E 48 8B 05 ? ? ? ?", offset = 4, rip = 3)]
+        world: usize,
+        // a byte set which has no value and mask
+        #[sig("[D7|C0] 3A 8C 51 E8", offset = 4, rip = 1, len = 5)]
+        update: usize,
+        #[sig("2E 48 8B 05", offset = -3)]
+        start: usize,
+    }
+
+    /// a page of code which loads a global and calls a function
+    fn code_page() -> Mapping {
+        let map = Mapping::new(0x1000);
+        map.write(0x100, &[0xC4, 0x6B, 0x91, 0x2E, 0x48, 0x8B, 0x05]);
+        // `mov rax, [rip + 0x6F5]` ends at 0x10B
+        map.write(0x107, &0x6F5i32.to_ne_bytes());
+        map.write(0x200, &[0xD7, 0x3A, 0x8C, 0x51, 0xE8]);
+        // `call -0x109` ends at 0x209
+        map.write(0x205, &(-0x109i32).to_ne_bytes());
+        map
+    }
+
+    #[test]
+    fn test_signature_table() {
+        let map = code_page();
+        let module = map.module("code");
+        let page = map.start();
+        let offsets = Offsets::resolve(&module).unwrap();
+        assert_eq!(offsets.world, page + 0x800);
+        assert_eq!(offsets.update, page + 0x100);
+        assert_eq!(offsets.start, page + 0x100);
+
+        let mut table = SignatureTable::new();
+        table.add("world", "C4 6B 91 2E 48 8B 05", 4, Some((3, 7)));
+        table.add("missing", "C4 6B 91 2E 48 8B 06", 0, None);
+        table.add("invalid", "C4 6B 9", 0, None);
+        table.add("ambiguous", "00 00 00 00", 0, None);
+        let Err(SignatureTableError::Failed(failures)) = table.resolve(&module) else {
+            panic!("expected every failing signature to be reported");
+        };
+        assert_eq!(failures.len(), 3);
+        assert!(matches!(
+            &failures[0],
+            SignatureFailure::Match(SetMatchError::Missing(name)) if name == "missing"
+        ));
+        assert!(matches!(&failures[1], SignatureFailure::Invalid(name, _) if name == "invalid"));
+        assert!(matches!(
+            &failures[2],
+            SignatureFailure::Match(SetMatchError::Ambiguous(name, _)) if name == "ambiguous"
+        ));
+    }
+}
@@ -0,0 +1,620 @@
+mod spill;
+
+use std::{
+    fmt::Debug,
+    io::{BufRead, Read},
+    marker::PhantomData,
+};
+
+use spill::{read_varint, write_varint, Spill};
+
+use super::chunks::ChunkReader;
+use crate::{
+    structures::regions::RegionFilter,
+    traits::{Mem, MemError},
+};
+
+/// how far apart results can be to still be read in one go during a next scan
+const BATCH_SIZE: usize = 0x1_0000;
+/// the smallest page size, memory which faults is skipped up to the end of its page
+const PAGE_SIZE: usize = 0x1000;
+
+/// A value which can be scanned for by a [`ValueScanner`]
+pub trait ScanValue: Copy + PartialOrd + Debug {
+    /// the size of the value in bytes
+    const SIZE: usize;
+    /// read the value from the start of <bytes>
+    fn from_bytes(bytes: &[u8]) -> Self;
+    /// write the value to <out>
+    fn to_bytes(self, out: &mut Vec<u8>);
+    /// is the value within <tolerance> of <target>
+    fn within(self, target: Self, tolerance: Self) -> bool;
+    /// are the values bitwise identical
+    fn same(self, other: Self) -> bool;
+}
+
+macro_rules! impl_scan_value {
+    ($($ty:ty => |$a:ident, $b:ident, $tolerance:ident| $within:expr),* $(,)?) => {$(
+        impl ScanValue for $ty {
+            const SIZE: usize = std::mem::size_of::<$ty>();
+            fn from_bytes(bytes: &[u8]) -> Self {
+                <$ty>::from_ne_bytes(bytes[..Self::SIZE].try_into().unwrap())
+            }
+            fn to_bytes(self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_ne_bytes());
+            }
+            fn within(self, target: Self, tolerance: Self) -> bool {
+                let ($a, $b, $tolerance) = (self, target, tolerance);
+                $within
+            }
+            fn same(self, other: Self) -> bool {
+                self.to_ne_bytes() == other.to_ne_bytes()
+            }
+        }
+    )*};
+}
+impl_scan_value!(
+    u8 => |a, b, tolerance| a.abs_diff(b) <= tolerance,
+    u16 => |a, b, tolerance| a.abs_diff(b) <= tolerance,
+    u32 => |a, b, tolerance| a.abs_diff(b) <= tolerance,
+    u64 => |a, b, tolerance| a.abs_diff(b) <= tolerance,
+    i8 => |a, b, tolerance| a.abs_diff(b) <= tolerance.unsigned_abs(),
+    i16 => |a, b, tolerance| a.abs_diff(b) <= tolerance.unsigned_abs(),
+    i32 => |a, b, tolerance| a.abs_diff(b) <= tolerance.unsigned_abs(),
+    i64 => |a, b, tolerance| a.abs_diff(b) <= tolerance.unsigned_abs(),
+    f32 => |a, b, tolerance| (a - b).abs() <= tolerance,
+    f64 => |a, b, tolerance| (a - b).abs() <= tolerance,
+);
+
+/// The condition of the first scan of a [`ValueScanner`]
+#[derive(Debug, Clone, Copy)]
+pub enum FirstScan<T> {
+    /// the value is exactly <T>
+    Exact(T),
+    /// the value is within the tolerance (the second value) of the first value, mostly for floats
+    Approx(T, T),
+    /// the value is within the inclusive range
+    Range(T, T),
+    /// the value is not known yet, every address is kept so following scans can compare against it
+    Unknown,
+}
+
+/// The condition of a following scan of a [`ValueScanner`], compared against the value from the previous scan
+#[derive(Debug, Clone, Copy)]
+pub enum NextScan<T> {
+    /// the value is exactly <T>
+    Exact(T),
+    /// the value is within the tolerance (the second value) of the first value, mostly for floats
+    Approx(T, T),
+    /// the value is within the inclusive range
+    Range(T, T),
+    /// the value has changed
+    Changed,
+    /// the value has not changed
+    Unchanged,
+    /// the value has increased
+    Increased,
+    /// the value has decreased
+    Decreased,
+}
+
+impl<T: ScanValue> FirstScan<T> {
+    fn matches(&self, new: T) -> bool {
+        match *self {
+            Self::Exact(x) => new == x,
+            Self::Approx(x, tolerance) => new.within(x, tolerance),
+            Self::Range(low, high) => low <= new && new <= high,
+            Self::Unknown => true,
+        }
+    }
+}
+
+impl<T: ScanValue> NextScan<T> {
+    fn matches(&self, old: T, new: T) -> bool {
+        match *self {
+            Self::Exact(x) => new == x,
+            Self::Approx(x, tolerance) => new.within(x, tolerance),
+            Self::Range(low, high) => low <= new && new <= high,
+            Self::Changed => !new.same(old),
+            Self::Unchanged => new.same(old),
+            Self::Increased => new > old,
+            Self::Decreased => new < old,
+        }
+    }
+}
+
+/// Value scan failures
+#[derive(Debug, thiserror::Error)]
+pub enum ValueScanError {
+    /// a next scan was done before the first scan
+    #[error("no first scan has been done")]
+    NoFirstScan,
+    /// reading the process failed
+    #[error(transparent)]
+    Mem(#[from] MemError),
+    /// storing the results failed
+    #[error("unable to store results: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// the results of the last scan
+enum Results {
+    /// a copy of the memory, every aligned address is a result.
+    /// stored as blocks of the start address, the length and the bytes.
+    Snapshot(Spill),
+    /// the address of every result and its value.
+    /// stored as the distance from the previous address as a varint followed by the value.
+    List(Spill),
+}
+
+/// A Cheat Engine style value scan session, every scan narrows down the results of the previous one.
+/// results are stored compactly and moved to a temporary file when they grow past [`ValueScanner::memory_limit`].
+/// ```no_run
+/// use poggers::sigscan::{FirstScan, NextScan, ValueScanner};
+/// use poggers::structures::process::Process;
+/// let process = Process::find_name("game").unwrap();
+/// let mut scanner = ValueScanner::<_, i32>::new(&process);
+/// scanner.first_scan(FirstScan::Exact(100)).unwrap();
+/// // take some damage
+/// scanner.next_scan(NextScan::Decreased).unwrap();
+/// for (addr, health) in scanner.results().unwrap() {
+///     println!("{addr:X} = {health}");
+/// }
+/// ```
+pub struct ValueScanner<'a, M: Mem, T: ScanValue> {
+    mem: &'a M,
+    filter: RegionFilter,
+    alignment: usize,
+    memory_limit: usize,
+    results: Option<Results>,
+    len: usize,
+    _value: PhantomData<T>,
+}
+
+impl<'a, M: Mem, T: ScanValue> ValueScanner<'a, M, T> {
+    /// create a session which scans the writable regions of <mem> at addresses aligned to the size of <T>
+    pub fn new(mem: &'a M) -> Self {
+        Self {
+            mem,
+            filter: RegionFilter::new().writable(),
+            alignment: T::SIZE,
+            memory_limit: 0x400_0000,
+            results: None,
+            len: 0,
+            _value: PhantomData,
+        }
+    }
+    /// scan the regions selected by <filter> instead of every writable region
+    pub fn filter(mut self, filter: RegionFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+    /// only check addresses which are a multiple of <alignment>, 1 checks every address
+    pub fn alignment(mut self, alignment: usize) -> Self {
+        self.alignment = alignment.max(1);
+        self
+    }
+    /// how many bytes of results are kept in memory before they are moved to a temporary file, 64MiB by default
+    pub fn memory_limit(mut self, bytes: usize) -> Self {
+        self.memory_limit = bytes;
+        self
+    }
+    /// the amount of results from the last scan
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// are there no results, also true before the first scan
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// have the results been moved to a temporary file
+    pub fn is_spilled(&self) -> bool {
+        match &self.results {
+            Some(Results::Snapshot(spill) | Results::List(spill)) => spill.is_spilled(),
+            None => false,
+        }
+    }
+    /// the first aligned address at or after <addr>
+    fn align(&self, addr: usize) -> usize {
+        addr.next_multiple_of(self.alignment)
+    }
+    /// scan the regions for the first time, replacing any previous results
+    pub fn first_scan(&mut self, scan: FirstScan<T>) -> Result<usize, ValueScanError> {
+        self.results = None;
+        self.len = 0;
+        let mut chunks = ChunkReader::filtered(self.mem, &self.filter, T::SIZE - 1)?;
+        let mut spill = Spill::new(self.memory_limit);
+        let mut writer = ListWriter::new();
+        // chunks overlap, so addresses before this have been checked already
+        let mut next = 0;
+        // results in the snapshot, a list counts its own
+        let mut snapshot_len = 0;
+        while let Some((addr, data)) = chunks.next_chunk()? {
+            let from = addr.max(next);
+            next = (addr + data.len() + 1).saturating_sub(T::SIZE);
+            if let FirstScan::Unknown = scan {
+                let data = &data[from - addr..];
+                spill.write(&(from as u64).to_ne_bytes())?;
+                spill.write(&(data.len() as u64).to_ne_bytes())?;
+                spill.write(data)?;
+                snapshot_len += self.snapshot_positions(from, data.len()).count();
+                continue;
+            }
+            for at in self.positions(from, addr, data.len()) {
+                let value = T::from_bytes(&data[at - addr..]);
+                if scan.matches(value) {
+                    writer.push(&mut spill, at, value)?;
+                }
+            }
+        }
+        (self.results, self.len) = match scan {
+            FirstScan::Unknown => (Some(Results::Snapshot(spill)), snapshot_len),
+            _ => (Some(Results::List(spill)), writer.count),
+        };
+        Ok(self.len)
+    }
+    /// the aligned addresses from <from> where a whole value fits in the <len> bytes at <addr>
+    fn positions(&self, from: usize, addr: usize, len: usize) -> impl Iterator<Item = usize> {
+        let end = (addr + len + 1).saturating_sub(T::SIZE);
+        (self.align(from)..end).step_by(self.alignment)
+    }
+    /// the aligned addresses in a snapshot block
+    fn snapshot_positions(&self, start: usize, len: usize) -> impl Iterator<Item = usize> {
+        self.positions(start, start, len)
+    }
+    /// narrow down the results of the previous scan
+    pub fn next_scan(&mut self, scan: NextScan<T>) -> Result<usize, ValueScanError> {
+        let mut results = self.results.take().ok_or(ValueScanError::NoFirstScan)?;
+        let mut spill = Spill::new(self.memory_limit);
+        let mut writer = ListWriter::new();
+        let res = match &mut results {
+            Results::Snapshot(old) => self.next_snapshot(old, scan, &mut spill, &mut writer),
+            Results::List(old) => self.next_list(old, scan, &mut spill, &mut writer),
+        };
+        if let Err(err) = res {
+            // keep the previous results so the scan can be retried
+            self.results = Some(results);
+            return Err(err);
+        }
+        self.results = Some(Results::List(spill));
+        self.len = writer.count;
+        Ok(self.len)
+    }
+    fn next_snapshot(
+        &self,
+        old: &mut Spill,
+        scan: NextScan<T>,
+        spill: &mut Spill,
+        writer: &mut ListWriter,
+    ) -> Result<(), ValueScanError> {
+        let mut reader = old.reader()?;
+        let mut before = Vec::new();
+        let mut now = Vec::new();
+        while let Some((start, len)) = read_block_header(&mut reader)? {
+            before.resize(len, 0);
+            reader.read_exact(&mut before)?;
+            now.resize(len, 0);
+            let mut offset = 0;
+            while offset < len {
+                let from = start + offset;
+                let read = self.read_readable(from, &mut now[offset..])?;
+                for at in self.positions(from, start, offset + read) {
+                    let (old, new) = (
+                        T::from_bytes(&before[at - start..]),
+                        T::from_bytes(&now[at - start..]),
+                    );
+                    if writer.last.is_none_or(|last| at > last) && scan.matches(old, new) {
+                        writer.push(spill, at, new)?;
+                    }
+                }
+                // continue after the page which could not be read
+                offset = ((from + read) | (PAGE_SIZE - 1)) + 1 - start;
+            }
+        }
+        Ok(())
+    }
+    /// read as much of <data> at <addr> as possible, returns the amount of bytes read.
+    /// memory which can not be read is part of a scan, any other error is returned.
+    fn read_readable(&self, addr: usize, data: &mut [u8]) -> Result<usize, MemError> {
+        match unsafe { self.mem.read_partial(addr, data) } {
+            Ok(read) => Ok(read),
+            Err(e) if e.is_fault() => Ok(0),
+            Err(e) => Err(e),
+        }
+    }
+    /// compare every result of <batch> against its value now, reading the results after a fault again
+    fn next_batch(
+        &self,
+        batch: &[(usize, T)],
+        data: &mut Vec<u8>,
+        scan: NextScan<T>,
+        spill: &mut Spill,
+        writer: &mut ListWriter,
+    ) -> Result<(), ValueScanError> {
+        let mut rest = batch;
+        while let [(start, _), ..] = rest {
+            let start = *start;
+            data.resize(rest[rest.len() - 1].0 + T::SIZE - start, 0);
+            let read = self.read_readable(start, data)?;
+            let readable = rest
+                .iter()
+                .take_while(|(addr, _)| addr + T::SIZE - start <= read)
+                .count();
+            for &(addr, old) in &rest[..readable] {
+                let new = T::from_bytes(&data[addr - start..]);
+                if scan.matches(old, new) {
+                    writer.push(spill, addr, new)?;
+                }
+            }
+            // skip the results in the page which could not be read
+            let resume = ((start + read) | (PAGE_SIZE - 1)) + 1;
+            rest = &rest[readable..];
+            rest = &rest[rest.iter().take_while(|(addr, _)| *addr < resume).count()..];
+        }
+        Ok(())
+    }
+    fn next_list(
+        &self,
+        old: &mut Spill,
+        scan: NextScan<T>,
+        spill: &mut Spill,
+        writer: &mut ListWriter,
+    ) -> Result<(), ValueScanError> {
+        let mut reader = ListReader::<T>::new(old.reader()?);
+        let mut batch: Vec<(usize, T)> = Vec::new();
+        let mut data = Vec::new();
+        loop {
+            let next = reader.next()?;
+            let full = match (next, batch.first()) {
+                (Some((addr, _)), Some((start, _))) => addr + T::SIZE - start > BATCH_SIZE,
+                (None, _) => true,
+                _ => false,
+            };
+            if full && !batch.is_empty() {
+                // read every value in the batch at once
+                self.next_batch(&batch, &mut data, scan, spill, writer)?;
+                batch.clear();
+            }
+            match next {
+                Some(result) => batch.push(result),
+                None => return Ok(()),
+            }
+        }
+    }
+    /// iterate over the address and value of every result, as of the last scan.
+    /// the iteration stops early if the results could not be read back from the temporary file.
+    pub fn results(&mut self) -> Result<impl Iterator<Item = (usize, T)> + '_, ValueScanError> {
+        let (alignment, results) = (self.alignment, self.results.as_mut());
+        let iter: Box<dyn Iterator<Item = (usize, T)> + '_> = match results {
+            None => Box::new(std::iter::empty()),
+            Some(Results::List(spill)) => {
+                let mut reader = ListReader::<T>::new(spill.reader()?);
+                Box::new(std::iter::from_fn(move || reader.next().ok().flatten()))
+            }
+            Some(Results::Snapshot(spill)) => {
+                let mut reader = spill.reader()?;
+                let mut block = Vec::new();
+                Box::new(
+                    std::iter::from_fn(move || {
+                        let (start, len) = read_block_header(&mut reader).ok().flatten()?;
+                        block.resize(len, 0);
+                        reader.read_exact(&mut block).ok()?;
+                        let end = (start + len + 1).saturating_sub(T::SIZE);
+                        let values = (start.next_multiple_of(alignment)..end)
+                            .step_by(alignment)
+                            .map(|at| (at, T::from_bytes(&block[at - start..])))
+                            .collect::<Vec<_>>();
+                        Some(values)
+                    })
+                    .flatten(),
+                )
+            }
+        };
+        Ok(iter)
+    }
+}
+
+/// reads the start address and length of a snapshot block, none at the end of the snapshot
+fn read_block_header(reader: &mut dyn BufRead) -> std::io::Result<Option<(usize, usize)>> {
+    let mut header = [0u8; 16];
+    if reader.fill_buf()?.is_empty() {
+        return Ok(None);
+    }
+    reader.read_exact(&mut header)?;
+    let start = u64::from_ne_bytes(header[..8].try_into().unwrap());
+    let len = u64::from_ne_bytes(header[8..].try_into().unwrap());
+    Ok(Some((start as usize, len as usize)))
+}
+
+/// writes results as the distance from the previous address followed by the value
+struct ListWriter {
+    last: Option<usize>,
+    count: usize,
+    value: Vec<u8>,
+}
+
+impl ListWriter {
+    fn new() -> Self {
+        Self {
+            last: None,
+            count: 0,
+            value: Vec::new(),
+        }
+    }
+    fn push<T: ScanValue>(
+        &mut self,
+        spill: &mut Spill,
+        addr: usize,
+        value: T,
+    ) -> std::io::Result<()> {
+        write_varint(spill, (addr - self.last.unwrap_or(0)) as u64)?;
+        self.value.clear();
+        value.to_bytes(&mut self.value);
+        spill.write(&self.value)?;
+        self.last = Some(addr);
+        self.count += 1;
+        Ok(())
+    }
+}
+
+/// reads results written by [`ListWriter`]
+struct ListReader<'r, T> {
+    reader: Box<dyn BufRead + 'r>,
+    last: usize,
+    value: Vec<u8>,
+    _value: PhantomData<T>,
+}
+
+impl<'r, T: ScanValue> ListReader<'r, T> {
+    fn new(reader: Box<dyn BufRead + 'r>) -> Self {
+        Self {
+            reader,
+            last: 0,
+            value: vec![0; T::SIZE],
+            _value: PhantomData,
+        }
+    }
+    fn next(&mut self) -> std::io::Result<Option<(usize, T)>> {
+        let Some(distance) = read_varint(&mut self.reader)? else {
+            return Ok(None);
+        };
+        self.reader.read_exact(&mut self.value)?;
+        self.last += distance as usize;
+        Ok(Some((self.last, T::from_bytes(&self.value))))
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::{FirstScan, NextScan, ValueScanError, ValueScanner};
+    use crate::{
+        structures::{process::Process, regions::RegionFilter},
+        testing::Mapping,
+    };
+
+    fn range<T>(values: &[T]) -> RegionFilter {
+        let start = values.as_ptr() as usize;
+        RegionFilter::new().range(start..start + size_of_val(values))
+    }
+    fn addr<T>(values: &[T], index: usize) -> usize {
+        &values[index] as *const T as usize
+    }
+
+    #[test]
+    fn test_exact_then_next() {
+        let mut map = Mapping::new(0x2000);
+        let ex = map.process();
+        let values = map.values::<i32>();
+        for i in [3, 700, 1500] {
+            values[i] = 100;
+        }
+        let mut scanner = ValueScanner::<_, i32>::new(&ex).filter(range(values));
+        assert!(matches!(
+            scanner.next_scan(NextScan::Changed),
+            Err(ValueScanError::NoFirstScan)
+        ));
+        assert_eq!(scanner.first_scan(FirstScan::Exact(100)).unwrap(), 3);
+
+        values[3] = 90;
+        values[1500] = 110;
+        assert_eq!(scanner.next_scan(NextScan::Changed).unwrap(), 2);
+        // compared against the values from the previous scan
+        values[3] = 85;
+        values[1500] = 120;
+        assert_eq!(scanner.next_scan(NextScan::Decreased).unwrap(), 1);
+        assert_eq!(
+            scanner.results().unwrap().collect::<Vec<_>>(),
+            [(addr(values, 3), 85)]
+        );
+        values[3] = 80;
+        assert_eq!(scanner.next_scan(NextScan::Exact(80)).unwrap(), 1);
+        assert_eq!(scanner.next_scan(NextScan::Unchanged).unwrap(), 1);
+        assert_eq!(scanner.next_scan(NextScan::Increased).unwrap(), 0);
+    }
+    #[test]
+    fn test_unknown_spilled() {
+        let mut map = Mapping::new(0x4000);
+        let ex = map.process();
+        let values = map.values::<u16>();
+        let mut scanner = ValueScanner::<_, u16>::new(&ex)
+            .filter(range(values))
+            .memory_limit(0x1000);
+        assert_eq!(
+            scanner.first_scan(FirstScan::Unknown).unwrap(),
+            values.len()
+        );
+        assert!(scanner.is_spilled());
+
+        values[10] = 5;
+        values[values.len() - 1] = 7;
+        assert_eq!(scanner.next_scan(NextScan::Changed).unwrap(), 2);
+        assert_eq!(
+            scanner.results().unwrap().collect::<Vec<_>>(),
+            [(addr(values, 10), 5), (addr(values, values.len() - 1), 7)]
+        );
+        assert_eq!(scanner.next_scan(NextScan::Range(6, 8)).unwrap(), 1);
+    }
+    #[test]
+    fn test_float_and_alignment() {
+        let mut map = Mapping::new(0x1000);
+        let ex = map.process();
+        let bytes = map.values::<u8>();
+        // unaligned, so only found when every address is checked
+        bytes[5..9].copy_from_slice(&1.5f32.to_ne_bytes());
+        bytes[16..20].copy_from_slice(&1.501f32.to_ne_bytes());
+
+        let mut scanner = ValueScanner::<_, f32>::new(&ex).filter(range(bytes));
+        assert_eq!(scanner.first_scan(FirstScan::Approx(1.5, 0.01)).unwrap(), 1);
+        let mut scanner = scanner.alignment(1);
+        assert_eq!(scanner.first_scan(FirstScan::Approx(1.5, 0.01)).unwrap(), 2);
+        assert_eq!(scanner.first_scan(FirstScan::Range(1.4, 1.5)).unwrap(), 1);
+        assert_eq!(scanner.results().unwrap().next().unwrap().0, addr(bytes, 5));
+    }
+    #[test]
+    fn test_next_scan_skips_faults() {
+        let mut map = Mapping::new(0x3000);
+        let ex = map.process();
+        let values = map.values::<i32>();
+        for i in [3, 1100, 2100] {
+            values[i] = 100;
+        }
+        let mut list = ValueScanner::<_, i32>::new(&ex).filter(range(values));
+        assert_eq!(list.first_scan(FirstScan::Exact(100)).unwrap(), 3);
+        let mut snapshot = ValueScanner::<_, i32>::new(&ex).filter(range(values));
+        snapshot.first_scan(FirstScan::Unknown).unwrap();
+
+        // a guard page between results which are read in one batch
+        let guard = addr(values, 1024) as *mut libc::c_void;
+        unsafe { libc::mprotect(guard, 0x1000, libc::PROT_NONE) };
+        assert_eq!(list.next_scan(NextScan::Unchanged).unwrap(), 2);
+        assert_eq!(
+            list.results()
+                .unwrap()
+                .map(|(at, _)| at)
+                .collect::<Vec<_>>(),
+            [addr(values, 3), addr(values, 2100)]
+        );
+        assert_eq!(snapshot.next_scan(NextScan::Unchanged).unwrap(), 2048);
+        assert_eq!(snapshot.next_scan(NextScan::Exact(100)).unwrap(), 2);
+        unsafe { libc::mprotect(guard, 0x1000, libc::PROT_READ | libc::PROT_WRITE) };
+    }
+    #[test]
+    fn test_process_exited() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("10")
+            .spawn()
+            .unwrap();
+        let process = Process::find_pid(child.id()).unwrap();
+        let mut scanner = ValueScanner::<_, u64>::new(&process);
+        let found = scanner.first_scan(FirstScan::Exact(0)).unwrap();
+        assert!(found > 0);
+        child.kill().unwrap();
+        child.wait().unwrap();
+        assert!(matches!(
+            scanner.next_scan(NextScan::Unchanged),
+            Err(ValueScanError::Mem(e)) if !e.is_fault()
+        ));
+        // the results of the previous scan are kept
+        assert_eq!(scanner.len(), found);
+    }
+}
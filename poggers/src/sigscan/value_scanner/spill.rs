@@ -0,0 +1,184 @@
+use std::{
+    fs::{File, OpenOptions},
+    hash::{BuildHasher, RandomState},
+    io::{self, BufRead, BufReader, BufWriter, Cursor, Seek, SeekFrom, Write},
+    path::PathBuf,
+};
+
+/// a temporary file which is deleted when dropped
+struct TempFile {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    /// a reader moved the shared offset of the file, so the next write has to seek back to the end
+    rewound: bool,
+}
+
+impl TempFile {
+    /// create a new file with a random name which is only accessible by the current user, never opening a file
+    /// (or symlink) which already exists
+    fn create() -> io::Result<Self> {
+        let random = RandomState::new();
+        loop {
+            let path = std::env::temp_dir().join(format!(
+                "poggers-scan-{}-{:016x}.bin",
+                std::process::id(),
+                random.hash_one(std::time::SystemTime::now())
+            ));
+            let mut options = OpenOptions::new();
+            options.write(true).read(true).create_new(true);
+            #[cfg(unix)]
+            std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+            match options.open(&path) {
+                Ok(file) => {
+                    return Ok(Self {
+                        path,
+                        writer: BufWriter::new(file),
+                        rewound: false,
+                    })
+                }
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        std::fs::remove_file(&self.path).ok();
+    }
+}
+
+/// an append only byte store which is kept in memory until it grows past <limit> bytes, after which it is moved to a
+/// temporary file
+pub(super) struct Spill {
+    limit: usize,
+    buf: Vec<u8>,
+    file: Option<TempFile>,
+}
+
+impl Spill {
+    pub(super) fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            buf: Vec::new(),
+            file: None,
+        }
+    }
+    /// is the store in a file
+    pub(super) fn is_spilled(&self) -> bool {
+        self.file.is_some()
+    }
+    pub(super) fn write(&mut self, bytes: &[u8]) -> io::Result<()> {
+        if let Some(file) = &mut self.file {
+            if file.rewound {
+                file.writer.seek(SeekFrom::End(0))?;
+                file.rewound = false;
+            }
+            return file.writer.write_all(bytes);
+        }
+        self.buf.extend_from_slice(bytes);
+        if self.buf.len() > self.limit {
+            let mut file = TempFile::create()?;
+            file.writer.write_all(&self.buf)?;
+            self.buf = Vec::new();
+            self.file = Some(file);
+        }
+        Ok(())
+    }
+    /// read everything which has been written so far
+    pub(super) fn reader(&mut self) -> io::Result<Box<dyn BufRead + '_>> {
+        match &mut self.file {
+            Some(file) => {
+                file.writer.flush()?;
+                // the clone shares the offset with the writer
+                let mut reader = file.writer.get_ref().try_clone()?;
+                reader.seek(SeekFrom::Start(0))?;
+                file.rewound = true;
+                Ok(Box::new(BufReader::new(reader)))
+            }
+            None => Ok(Box::new(Cursor::new(&self.buf))),
+        }
+    }
+}
+
+/// writes <value> as a LEB128 varint
+pub(super) fn write_varint(spill: &mut Spill, mut value: u64) -> io::Result<()> {
+    let mut bytes = [0u8; 10];
+    let mut len = 0;
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        bytes[len] = byte | if value != 0 { 0x80 } else { 0 };
+        len += 1;
+        if value == 0 {
+            return spill.write(&bytes[..len]);
+        }
+    }
+}
+
+/// reads a LEB128 varint, none at the end of the input
+pub(super) fn read_varint(reader: &mut dyn BufRead) -> io::Result<Option<u64>> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0u8];
+        if reader.read(&mut byte)? == 0 {
+            return match shift {
+                0 => Ok(None),
+                _ => Err(io::ErrorKind::UnexpectedEof.into()),
+            };
+        }
+        value |= ((byte[0] & 0x7F) as u64) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(Some(value));
+        }
+    }
+    Err(io::ErrorKind::InvalidData.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::{read_varint, write_varint, Spill};
+
+    #[test]
+    fn test_spill_to_file() {
+        let mut spill = Spill::new(16);
+        for x in [0u64, 1, 0x7F, 0x80, 0x3FFF, 0x4000, u64::MAX] {
+            write_varint(&mut spill, x).unwrap();
+        }
+        assert!(spill.is_spilled());
+        spill.write(&[1, 2, 3]).unwrap();
+        let mut reader = spill.reader().unwrap();
+        for x in [0u64, 1, 0x7F, 0x80, 0x3FFF, 0x4000, u64::MAX] {
+            assert_eq!(read_varint(&mut reader).unwrap(), Some(x));
+        }
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, [1, 2, 3]);
+        drop(reader);
+
+        // writing after reading appends instead of overwriting
+        spill.write(&[4]).unwrap();
+        let mut all = Vec::new();
+        spill.reader().unwrap().read_to_end(&mut all).unwrap();
+        assert_eq!(all[all.len() - 4..], [1, 2, 3, 4]);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let path = &spill.file.as_ref().unwrap().path;
+            let mode = std::fs::metadata(path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+    #[test]
+    fn test_in_memory() {
+        let mut spill = Spill::new(1024);
+        write_varint(&mut spill, 300).unwrap();
+        assert!(!spill.is_spilled());
+        let mut reader = spill.reader().unwrap();
+        assert_eq!(read_varint(&mut reader).unwrap(), Some(300));
+        assert_eq!(read_varint(&mut reader).unwrap(), None);
+    }
+}
@@ -1,6 +1,27 @@
+mod path;
+mod remote_ptr;
+mod remote_struct;
+
+pub use path::PointerPath;
+pub use remote_ptr::{Pointer, RemotePtr};
+pub use remote_struct::RemoteStruct;
+
 use crate::{sigscan::SigScan, traits::MemError};
 
-/// represents an address in a process
+/// represents an address in a process.
+/// the address can be resolved further from a scan hit, e.g. for `E8 ? ? ? ? 48 8B 05 ? ? ? ?`:
+/// ```no_run
+/// use poggers::structures::process::{implement::utils::ProcessUtils, Process};
+/// use poggers::traits::Mem;
+/// let process = Process::find_name("game").unwrap();
+/// let module = process.get_base_module().unwrap();
+/// let hit = module.scan("E8 ? ? ? ? 48 8B 05 ? ? ? ?").unwrap().unwrap();
+/// unsafe {
+///     let function = process.address(hit).call_target().unwrap();
+///     let global = process.address(hit).offset(5).rip_relative(3, 7).unwrap();
+///     let value = global.deref().unwrap().get_address();
+/// }
+/// ```
 pub struct Address<'a, T: SigScan> {
     at: usize,
     owner: &'a T,
@@ -27,6 +48,57 @@ impl<'a, T: SigScan> Address<'a, T> {
     pub fn goto(&mut self, to: usize) {
         self.at = to;
     }
+    /// get the address
+    pub const fn get_address(&self) -> usize {
+        self.at
+    }
+    /// a [`RemotePtr`] to a <V> at the address
+    pub const fn typed<V>(&self) -> RemotePtr<'a, T, V> {
+        RemotePtr::new(self.owner, self.at)
+    }
+    /// move the address by <offset> bytes, which may be negative
+    pub const fn offset(mut self, offset: isize) -> Self {
+        self.at = self.at.wrapping_add_signed(offset);
+        self
+    }
+    /// follow the pointer stored at the address, read with the pointer width of the process
+    /// # Safety
+    /// This function is unsafe because it can read from any address in the process.
+    pub unsafe fn deref(mut self) -> Result<Self, MemError> {
+        self.at = self.owner.read_pointer(self.at)?;
+        Ok(self)
+    }
+    /// follow a chain of pointers from the address, adding <offsets> at each level, see [`PointerPath`].
+    /// e.g. `follow(&[0x10, 0x8])` goes to `[[addr] + 0x10] + 0x8`
+    /// # Safety
+    /// This function is unsafe because it can read from any address in the process.
+    pub unsafe fn follow(mut self, offsets: impl AsRef<[usize]>) -> Result<Self, MemError> {
+        self.at = PointerPath::new(offsets.as_ref()).resolve(self.owner, self.at)?;
+        Ok(self)
+    }
+    /// resolve a RIP relative operand of the instruction at the address, <disp_off> is the offset of the `i32`
+    /// displacement into the instruction and <insn_len> the length of the instruction.
+    /// e.g. `48 8B 05 ? ? ? ?` (`mov rax, [rip + disp]`) is resolved with `rip_relative(3, 7)`
+    /// # Safety
+    /// This function is unsafe because it can read from any address in the process.
+    pub unsafe fn rip_relative(
+        mut self,
+        disp_off: usize,
+        insn_len: usize,
+    ) -> Result<Self, MemError> {
+        let disp = self.owner.read::<i32>(self.at + disp_off)?;
+        self.at = (self.at + insn_len).wrapping_add_signed(disp as isize);
+        Ok(self)
+    }
+    /// follow the target of the relative `call` (`E8`) or `jmp` (`E9`) at the address
+    /// # Safety
+    /// This function is unsafe because it can read from any address in the process.
+    pub unsafe fn call_target(self) -> Result<Self, MemError> {
+        match self.read::<u8>()? {
+            0xE8 | 0xE9 => self.rip_relative(1, 5),
+            opcode => Err(MemError::UnexpectedInstruction(self.at, opcode)),
+        }
+    }
 }
 impl<'a, T: SigScan> Clone for Address<'a, T> {
     fn clone(&self) -> Self {
@@ -64,3 +136,67 @@ impl<'a, T: SigScan> std::ops::Sub<usize> for Address<'a, T> {
         self
     }
 }
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use crate::{
+        structures::process::Process,
+        traits::{Mem, MemError},
+    };
+
+    #[test]
+    fn test_resolve() {
+        static TARGET: usize = 0x5EED;
+        let this = Process::this_process();
+        // `call rel32` followed by `mov rax, [rip + disp32]`
+        let mut code = vec![0xE8u8, 0, 0, 0, 0, 0x48, 0x8B, 0x05, 0, 0, 0, 0];
+        let start = code.as_ptr() as usize;
+        let call = (start as isize - (start as isize + 5)) as i32;
+        let mov = (&TARGET as *const usize as isize - (start as isize + 12)) as i32;
+        code[1..5].copy_from_slice(&call.to_le_bytes());
+        code[8..12].copy_from_slice(&mov.to_le_bytes());
+
+        unsafe {
+            let called = this.address(start).call_target().unwrap();
+            assert_eq!(called.get_address(), start);
+            let global = this.address(start).offset(5).rip_relative(3, 7).unwrap();
+            assert_eq!(global.get_address(), &TARGET as *const usize as usize);
+            assert_eq!(global.read::<usize>().unwrap(), 0x5EED);
+            assert!(matches!(
+                this.address(start).offset(5).call_target(),
+                Err(MemError::UnexpectedInstruction(_, 0x48))
+            ));
+        }
+        let pointer = &TARGET as *const usize as usize;
+        let deref = unsafe { this.address(&pointer as *const usize as usize).deref() };
+        assert_eq!(deref.unwrap().get_address(), pointer);
+        assert_eq!(
+            this.address(start).offset(-1).offset(2).get_address(),
+            start + 1
+        );
+    }
+    #[test]
+    fn test_follow() {
+        use super::PointerPath;
+
+        let target = [0usize, 0, 0x1337];
+        let middle = [0usize, &target as *const _ as usize];
+        let root = &middle as *const _ as usize;
+        let ex = Process::find_pid(std::process::id()).unwrap();
+        assert_eq!(ex.pointer_width(), std::mem::size_of::<usize>());
+        let base = &root as *const usize as usize;
+        unsafe {
+            let found = ex.address(base).follow([8, 0x10]).unwrap();
+            assert_eq!(found.get_address(), &target[2] as *const usize as usize);
+            assert_eq!(found.read::<usize>().unwrap(), 0x1337);
+
+            let path = PointerPath::new([8]).then(0x10);
+            assert_eq!(path.resolve(&ex, base).unwrap(), found.get_address());
+            // 0x1337 is not a valid pointer
+            match ex.address(base).follow([8, 0x10, 0, 0]) {
+                Err(MemError::PointerChainFailure(3, 0x1337, _)) => {}
+                other => panic!("expected the fourth level to fail, got {:?}", other.err()),
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use crate::traits::{read_pointer_sized, Mem, MemError};
+
+/// a reusable chain of offsets, followed from a base address like a Cheat Engine pointer.
+/// the pointer at the base is read and the first offset is added to it, then the pointer there is read and the next
+/// offset is added, and so on. the last offset is added without reading.
+/// ```no_run
+/// use poggers::structures::{addr::PointerPath, process::Process};
+/// use poggers::traits::Mem;
+/// let process = Process::find_name("game").unwrap();
+/// // [[[0x7FF612340000] + 0x10] + 0x8] + 0x30
+/// let health = PointerPath::new([0x10, 0x8, 0x30]);
+/// let addr = unsafe { health.resolve(&process, 0x7FF612340000) }.unwrap();
+/// let value = unsafe { process.read::<f32>(addr) }.unwrap();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct PointerPath {
+    offsets: Vec<usize>,
+}
+
+impl PointerPath {
+    /// create a path which adds <offsets> at each level
+    pub fn new(offsets: impl Into<Vec<usize>>) -> Self {
+        Self {
+            offsets: offsets.into(),
+        }
+    }
+    /// add another level to the path
+    pub fn then(mut self, offset: usize) -> Self {
+        self.offsets.push(offset);
+        self
+    }
+    /// Get the offsets added at each level
+    pub fn get_offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+    /// follow the path from <base> in <mem>, reading pointers with the [`Mem::pointer_width`] of <mem>.
+    /// # Returns
+    /// * the address the path ends at, or [`MemError::PointerChainFailure`] with the level which could not be read.
+    /// # Safety
+    /// This function is unsafe because it can read from any address in the process.
+    pub unsafe fn resolve<M: Mem + ?Sized>(&self, mem: &M, base: usize) -> Result<usize, MemError> {
+        let width = mem.pointer_width();
+        let mut addr = base;
+        for (level, offset) in self.offsets.iter().enumerate() {
+            let pointer = read_pointer_sized(mem, addr, width)
+                .map_err(|e| MemError::PointerChainFailure(level, addr, Box::new(e)))?;
+            addr = pointer.wrapping_add(*offset);
+        }
+        Ok(addr)
+    }
+}
+
+impl AsRef<[usize]> for PointerPath {
+    fn as_ref(&self) -> &[usize] {
+        &self.offsets
+    }
+}
+
+impl From<Vec<usize>> for PointerPath {
+    fn from(offsets: Vec<usize>) -> Self {
+        Self::new(offsets)
+    }
+}
+
+impl From<&[usize]> for PointerPath {
+    fn from(offsets: &[usize]) -> Self {
+        Self::new(offsets)
+    }
+}
+
+impl FromIterator<usize> for PointerPath {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect::<Vec<_>>())
+    }
+}
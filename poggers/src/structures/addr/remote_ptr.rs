@@ -0,0 +1,254 @@
+use std::{fmt, marker::PhantomData};
+
+use super::RemoteStruct;
+use crate::traits::{Mem, MemError};
+
+/// a pointer stored in the remote process, used as the pointee of a [`RemotePtr`] which points to a pointer.
+/// it has the size of a pointer in this process, so it only describes the pointers of processes with the same
+/// [`Mem::pointer_width`]. [`RemotePtr::deref`], [`RemotePtr::read_pointer`] and [`RemotePtr::write_pointer`] fail
+/// with [`MemError::PointerWidthMismatch`] for any other process, while [`RemotePtr::offset`], [`RemotePtr::read`]
+/// and [`RemotePtr::write`] always step and move the size of a pointer in this process.
+/// use [`Mem::read_pointer`] for pointers of e.g. a 32 bit process from a 64 bit one.
+#[repr(transparent)]
+pub struct Pointer<T> {
+    addr: usize,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T> Pointer<T> {
+    /// Get the address the pointer points to
+    pub const fn get_address(&self) -> usize {
+        self.addr
+    }
+    /// fail unless the pointers of <mem> have the size of a [`Pointer`]
+    pub fn check_width<M: Mem + ?Sized>(mem: &M) -> Result<(), MemError> {
+        match mem.pointer_width() {
+            width if width == std::mem::size_of::<Self>() => Ok(()),
+            width => Err(MemError::PointerWidthMismatch(width)),
+        }
+    }
+}
+
+impl<T> Clone for Pointer<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for Pointer<T> {}
+impl<T> PartialEq for Pointer<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.addr == other.addr
+    }
+}
+impl<T> Eq for Pointer<T> {}
+impl<T> fmt::Debug for Pointer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Pointer({:#X})", self.addr)
+    }
+}
+
+/// a typed pointer into a process, the counterpart of [`Address`](super::Address) which knows what it points to.
+/// ```no_run
+/// use poggers::structures::{addr::Pointer, process::Process};
+/// use poggers::traits::Mem;
+/// #[repr(C)]
+/// struct Player {
+///     health: f32,
+///     armor: f32,
+/// }
+/// let process = Process::find_name("game").unwrap();
+/// // a global array of pointers to players, `Pointer` is only valid if the game has the same pointer width
+/// let players = process.ptr::<Pointer<Player>>(0x7FF612340000);
+/// unsafe {
+///     let second = players.offset(1).deref().unwrap();
+///     let health = second.field::<f32>(0).read().unwrap();
+///     second.field::<f32>(4).write(&100.0).unwrap();
+/// }
+/// ```
+pub struct RemotePtr<'a, M: Mem, T> {
+    owner: &'a M,
+    addr: usize,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<'a, M: Mem, T> RemotePtr<'a, M, T> {
+    /// create a pointer to a <T> at <addr> in <owner>
+    pub const fn new(owner: &'a M, addr: usize) -> Self {
+        Self {
+            owner,
+            addr,
+            _type: PhantomData,
+        }
+    }
+    /// Get the address the pointer points to
+    pub const fn get_address(&self) -> usize {
+        self.addr
+    }
+    /// Get the process the pointer points into
+    pub const fn get_owner(&self) -> &'a M {
+        self.owner
+    }
+    /// is the pointer null
+    pub const fn is_null(&self) -> bool {
+        self.addr == 0
+    }
+    /// move the pointer by <count> values of <T>, which may be negative
+    pub const fn offset(self, count: isize) -> Self {
+        let bytes = count.wrapping_mul(std::mem::size_of::<T>() as isize);
+        Self::new(self.owner, self.addr.wrapping_add_signed(bytes))
+    }
+    /// a pointer to the <U> which is <offset> bytes into the <T>
+    pub const fn field<U>(self, offset: usize) -> RemotePtr<'a, M, U> {
+        RemotePtr::new(self.owner, self.addr.wrapping_add(offset))
+    }
+    /// treat the pointer as pointing to a <U> instead
+    pub const fn cast<U>(self) -> RemotePtr<'a, M, U> {
+        RemotePtr::new(self.owner, self.addr)
+    }
+    /// Read the value the pointer points to
+    /// # Safety
+    /// This function is unsafe because it can read from any address in the process.
+    pub unsafe fn read(&self) -> Result<T, MemError> {
+        self.owner.read(self.addr)
+    }
+    /// Write <value> to where the pointer points to
+    /// # Safety
+    /// This function is unsafe because it can write to any address in the process.
+    pub unsafe fn write(&self, value: &T) -> Result<(), MemError> {
+        self.owner.write(self.addr, value)
+    }
+}
+
+impl<'a, M: Mem, T: RemoteStruct> RemotePtr<'a, M, T> {
+    /// the view of the structure, with a getter and setter for every field
+    pub fn view(self) -> T::View<'a, M> {
+        T::view(self)
+    }
+}
+
+impl<'a, M: Mem, T> RemotePtr<'a, M, Pointer<T>> {
+    /// read the pointer this points to, failing if the process has a different pointer width
+    /// # Safety
+    /// This function is unsafe because it can read from any address in the process.
+    pub unsafe fn deref(&self) -> Result<RemotePtr<'a, M, T>, MemError> {
+        Ok(RemotePtr::new(
+            self.owner,
+            self.read_pointer()?.get_address(),
+        ))
+    }
+    /// Read the pointer this points to, failing if the process has a different pointer width
+    /// # Safety
+    /// This function is unsafe because it can read from any address in the process.
+    pub unsafe fn read_pointer(&self) -> Result<Pointer<T>, MemError> {
+        Pointer::<T>::check_width(self.owner)?;
+        self.read()
+    }
+    /// Write <value> to where the pointer points to, failing if the process has a different pointer width
+    /// # Safety
+    /// This function is unsafe because it can write to any address in the process.
+    pub unsafe fn write_pointer(&self, value: &Pointer<T>) -> Result<(), MemError> {
+        Pointer::<T>::check_width(self.owner)?;
+        self.write(value)
+    }
+}
+
+impl<M: Mem, T> Clone for RemotePtr<'_, M, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<M: Mem, T> Copy for RemotePtr<'_, M, T> {}
+impl<M: Mem, T> PartialEq for RemotePtr<'_, M, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.addr == other.addr
+    }
+}
+impl<M: Mem, T> fmt::Debug for RemotePtr<'_, M, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "RemotePtr<{}>({:#X})",
+            std::any::type_name::<T>(),
+            self.addr
+        )
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::Pointer;
+    use crate::{
+        structures::process::Process,
+        traits::{Mem, MemError},
+    };
+
+    #[repr(C)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Player {
+        id: u32,
+        health: f32,
+        position: [f32; 3],
+    }
+
+    #[test]
+    fn test_remote_ptr() {
+        let mut players = [
+            Player {
+                id: 1,
+                health: 100.0,
+                position: [1.0, 2.0, 3.0],
+            },
+            Player {
+                id: 2,
+                health: 50.0,
+                position: [4.0, 5.0, 6.0],
+            },
+        ];
+        let mut list = [
+            &players[1] as *const Player as usize,
+            &players[0] as *const _ as usize,
+        ];
+        let ex = Process::find_pid(std::process::id()).unwrap();
+
+        let first = ex.ptr::<Player>(players.as_mut_ptr() as usize);
+        let second = first.offset(1);
+        assert_eq!(
+            second.get_address() - first.get_address(),
+            size_of::<Player>()
+        );
+        assert_eq!(second.offset(-1), first);
+        unsafe {
+            assert_eq!(second.read().unwrap(), players[1]);
+            let health = second.field::<f32>(4);
+            assert_eq!(health.read().unwrap(), 50.0);
+            health.write(&75.0).unwrap();
+            let y = second.field::<[f32; 3]>(8).cast::<f32>().offset(1);
+            assert_eq!(y.read().unwrap(), 5.0);
+
+            let list = ex.ptr::<Pointer<Player>>(list.as_mut_ptr() as usize);
+            assert_eq!(list.deref().unwrap(), second);
+            assert_eq!(list.offset(1).deref().unwrap().read().unwrap().id, 1);
+        }
+        std::hint::black_box(&mut list);
+        assert_eq!(std::hint::black_box(&mut players)[1].health, 75.0);
+    }
+
+    #[test]
+    fn test_pointer_width_mismatch() {
+        let target = 0x1337usize;
+        let mut ex = Process::find_pid(std::process::id()).unwrap();
+        ex.width = 4;
+        let ptr = ex.ptr::<Pointer<u32>>(&target as *const usize as usize);
+        unsafe {
+            assert!(matches!(
+                ptr.deref(),
+                Err(MemError::PointerWidthMismatch(4))
+            ));
+            let value = ptr.read().unwrap();
+            assert!(matches!(
+                ptr.write_pointer(&value),
+                Err(MemError::PointerWidthMismatch(4))
+            ));
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use super::RemotePtr;
+use crate::traits::Mem;
+#[cfg(doc)]
+use crate::traits::MemError;
+
+/// a structure of a remote process, described by the offsets of its fields.
+/// implemented by `#[derive(RemoteStruct)]` from `poggers-derive`, which also generates a view type with a getter
+/// and setter for every field. fields which are `#[offset(n, nested)]` return the view of the nested structure, and
+/// [`Pointer`](super::Pointer) fields are followed to a [`RemotePtr`].
+/// a [`Pointer`](super::Pointer) takes up the size of a pointer in this process, so the getters, setters and the
+/// read of a structure with pointers fail with [`MemError::PointerWidthMismatch`] in a process with another width.
+/// ```ignore
+/// use poggers::structures::addr::Pointer;
+/// use poggers_derive::RemoteStruct;
+///
+/// #[derive(RemoteStruct)]
+/// struct Vec3 {
+///     #[offset(0x0)]
+///     x: f32,
+///     #[offset(0x4)]
+///     y: f32,
+///     #[offset(0x8)]
+///     z: f32,
+/// }
+///
+/// // the fields are checked to not overlap and to fit in the size at compile time
+/// #[derive(RemoteStruct)]
+/// #[remote(size = 0x200)]
+/// struct Player {
+///     #[offset(0x10)]
+///     health: f32,
+///     #[offset(0x18, nested)]
+///     position: Vec3,
+///     #[offset(0x1A8)]
+///     target: Pointer<Player>,
+/// }
+///
+/// let player = process.ptr::<Player>(0x1F2E3D4C50).view();
+/// unsafe {
+///     player.set_health(&100.0)?;
+///     let x = player.position().x()?;
+///     let target_health = player.target()?.view().health()?;
+/// }
+/// ```
+pub trait RemoteStruct: Sized {
+    /// the size of the structure in the remote process, which can differ from the size of the rust type
+    const SIZE: usize;
+    /// does the structure, or any structure nested in it, have [`Pointer`](super::Pointer) fields
+    const HAS_POINTERS: bool;
+    /// the generated view, which reads and writes the fields through a [`RemotePtr`]
+    type View<'a, M: Mem + 'a>;
+    /// create the view of the structure <ptr> points to
+    fn view<'a, M: Mem + 'a>(ptr: RemotePtr<'a, M, Self>) -> Self::View<'a, M>;
+    /// build the structure from its [`RemoteStruct::SIZE`] bytes in the remote process
+    /// # Safety
+    /// <bytes> has to be at least [`RemoteStruct::SIZE`] long, and the bytes of every field have to be valid for
+    /// its type
+    unsafe fn from_bytes(bytes: &[u8]) -> Self;
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use poggers_derive::RemoteStruct;
+
+    use crate::{
+        structures::{
+            addr::{Pointer, RemoteStruct},
+            process::Process,
+        },
+        traits::{Mem, MemError},
+    };
+
+    #[derive(RemoteStruct, Debug, Clone, Copy, PartialEq)]
+    struct Vec3 {
+        #[offset(0x0)]
+        x: f32,
+        #[offset(0x4)]
+        y: f32,
+        #[offset(0x8)]
+        z: f32,
+    }
+
+    #[derive(RemoteStruct)]
+    #[remote(size = 0x40)]
+    struct Player {
+        #[offset(0x30)]
+        target: Pointer<Player>,
+        #[offset(0x10)]
+        health: f32,
+        #[offset(0x18, nested)]
+        position: Vec3,
+    }
+
+    /// a player laid out like the remote structure
+    fn player(health: f32, position: [f32; 3], target: usize) -> [u8; 0x40] {
+        let mut bytes = [0u8; 0x40];
+        bytes[0x10..0x14].copy_from_slice(&health.to_ne_bytes());
+        for (i, x) in position.iter().enumerate() {
+            bytes[0x18 + i * 4..0x1C + i * 4].copy_from_slice(&x.to_ne_bytes());
+        }
+        bytes[0x30..0x38].copy_from_slice(&target.to_ne_bytes());
+        bytes
+    }
+
+    // only used through its view
+    #[allow(dead_code)]
+    #[derive(RemoteStruct)]
+    struct Team {
+        #[offset(0x0)]
+        size: u32,
+        #[offset(0x8, nested)]
+        leader: Player,
+    }
+
+    #[test]
+    fn test_remote_struct() {
+        assert_eq!(Vec3::SIZE, 12);
+        assert_eq!(Player::SIZE, 0x40);
+        const { assert!(!Vec3::HAS_POINTERS) };
+        const { assert!(Player::HAS_POINTERS && Team::HAS_POINTERS) };
+
+        let enemy = player(50.0, [4.0, 5.0, 6.0], 0);
+        let mut local = player(100.0, [1.0, 2.0, 3.0], enemy.as_ptr() as usize);
+        let ex = Process::find_pid(std::process::id()).unwrap();
+        let view = ex.ptr::<Player>(local.as_mut_ptr() as usize).view();
+        unsafe {
+            assert_eq!(view.health().unwrap(), 100.0);
+            assert_eq!(view.position().y().unwrap(), 2.0);
+            assert_eq!(
+                view.position_ptr().get_address(),
+                view.ptr().get_address() + 0x18
+            );
+
+            let target = view.target().unwrap().view();
+            assert_eq!(target.health().unwrap(), 50.0);
+            assert!(target.target().unwrap().is_null());
+            assert_eq!(
+                target.position().read().unwrap(),
+                Vec3 {
+                    x: 4.0,
+                    y: 5.0,
+                    z: 6.0
+                }
+            );
+
+            view.set_health(&75.0).unwrap();
+            view.position().set_z(&-3.0).unwrap();
+            let read = view.read().unwrap();
+            assert_eq!(read.health, 75.0);
+            assert_eq!(read.position.z, -3.0);
+            assert_eq!(read.target.get_address(), enemy.as_ptr() as usize);
+        }
+        let local = std::hint::black_box(&mut local);
+        assert_eq!(local[0x10..0x14], 75f32.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_pointer_width_mismatch() {
+        let mut local = [0u8; 0x48];
+        let mut ex = Process::find_pid(std::process::id()).unwrap();
+        ex.width = 4;
+        let team = ex.ptr::<Team>(local.as_mut_ptr() as usize).view();
+        let mismatch = |e| matches!(e, Err(MemError::PointerWidthMismatch(4)));
+        unsafe {
+            assert!(mismatch(team.leader().target().map(|_| ())));
+            let target = team.leader().target_ptr().read().unwrap();
+            assert!(mismatch(team.leader().set_target(&target)));
+            assert!(mismatch(team.read().map(|_| ())));
+            assert!(mismatch(team.leader().read().map(|_| ())));
+            // fields without pointers are still usable
+            assert_eq!(team.leader().position().read().unwrap().x, 0.0);
+            team.size().unwrap();
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use super::pod::Pod;
+use crate::traits::{Mem, MemError};
+
+/// Reads which are queued and then submitted at once with [`Mem::read_batch`], which on linux reads many of them
+/// with a single `process_vm_readv` call. useful when a lot of small values are refreshed every frame.
+/// ```no_run
+/// use poggers::structures::{batch::ReadBatch, process::{implement::utils::ProcessUtils, Process}};
+/// let process = Process::find_name("game").unwrap();
+/// let players = [0x7FF612340000usize, 0x7FF612350000];
+/// let mut health = [0f32; 2];
+/// let mut positions = [[0f32; 3]; 2];
+///
+/// let mut batch = ReadBatch::new();
+/// for ((player, health), position) in players.iter().zip(&mut health).zip(&mut positions) {
+///     batch.add_pod(player + 0x10, health);
+///     batch.add_pod(player + 0x18, position);
+/// }
+/// let results = unsafe { batch.submit(&process) };
+/// assert!(results.iter().all(Result::is_ok));
+/// ```
+#[derive(Debug, Default)]
+pub struct ReadBatch<'b> {
+    entries: Vec<(usize, &'b mut [u8])>,
+}
+
+impl<'b> ReadBatch<'b> {
+    /// create an empty batch
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// create an empty batch with room for <capacity> reads
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+    /// queue reading the memory at <addr> into <buf>, returns the index of its result
+    pub fn add(&mut self, addr: usize, buf: &'b mut [u8]) -> usize {
+        self.entries.push((addr, buf));
+        self.entries.len() - 1
+    }
+    /// queue reading the [`Pod`] <value> from <addr>, returns the index of its result
+    pub fn add_pod<T: Pod>(&mut self, addr: usize, value: &'b mut T) -> usize {
+        self.add_slice(addr, std::slice::from_mut(value))
+    }
+    /// queue reading every [`Pod`] in <values> from <addr>, returns the index of its result
+    pub fn add_slice<T: Pod>(&mut self, addr: usize, values: &'b mut [T]) -> usize {
+        let size = std::mem::size_of_val(values);
+        // any bytes are a valid pod, and it has no padding
+        let bytes = unsafe { std::slice::from_raw_parts_mut(values.as_mut_ptr() as *mut u8, size) };
+        self.add(addr, bytes)
+    }
+    /// the amount of queued reads
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    /// are there no queued reads
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    /// read every queued entry from <mem>, returns whether each one could be read in the order they were added
+    /// # Safety
+    /// This function is unsafe because it can read from any address in the process.
+    pub unsafe fn submit<M: Mem + ?Sized>(mut self, mem: &M) -> Vec<Result<(), MemError>> {
+        mem.read_batch(&mut self.entries)
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::ReadBatch;
+    use crate::{structures::process::Process, traits::MemError};
+
+    #[test]
+    fn test_read_batch() {
+        let values: Vec<u64> = (0..3000).map(|x| x * 3).collect();
+        let ex = Process::find_pid(std::process::id()).unwrap();
+        let base = values.as_ptr() as usize;
+
+        // more entries than fit in a single call, with unreadable ones in between
+        let mut read = vec![0u64; 3000];
+        let mut unreadable = [0u8; 4];
+        let mut empty = [0u8; 0];
+        let mut batch = ReadBatch::with_capacity(3002);
+        for (i, value) in read.iter_mut().enumerate() {
+            batch.add_pod(base + i * 8, value);
+        }
+        let bad = batch.add(8, &mut unreadable);
+        batch.add(0, &mut empty);
+        assert_eq!(batch.len(), 3002);
+        let results = unsafe { batch.submit(&ex) };
+        assert!(results[..3000].iter().all(Result::is_ok));
+        assert!(matches!(
+            results[bad],
+            Err(MemError::ReadFailure(8, 0, libc::EFAULT))
+        ));
+        assert!(results[bad + 1].is_ok());
+        assert_eq!(read, values);
+
+        let mut first = [0u64; 2];
+        let mut last = 0u32;
+        let mut batch = ReadBatch::new();
+        batch.add(0x10, &mut unreadable);
+        batch.add_slice(base, &mut first);
+        batch.add_pod(base + 2999 * 8, &mut last);
+        let results = unsafe { batch.submit(&ex) };
+        assert!(results[0].is_err());
+        assert!(results[1..].iter().all(Result::is_ok));
+        assert_eq!(first, [0, 3]);
+        assert_eq!(last, 2999 * 3);
+    }
+}
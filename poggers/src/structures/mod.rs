@@ -1,20 +1,22 @@
 /// wrapper around a address
 pub mod addr;
-#[feature(modules)]
+/// reading many buffers at once
+pub mod batch;
 /// a module in a process
 pub mod modules;
-#[feature(processes)]
+/// plain old data, which can be read from any bytes
+pub mod pod;
 /// an alternative to create_snapshot, just list through all processes running
 pub mod proc_list;
-#[feature(processes)]
 /// process
 pub mod process;
 /// protections for memory
 pub mod protections;
+/// regions of mapped memory
+pub mod regions;
 /// helper for allocated virtual memory
 pub mod virtalloc;
 
 #[cfg(windows)]
-#[feature(snapshot)]
 /// a wrapper for the win32 snapshottool api
 pub mod create_snapshot;
@@ -1,4 +1,12 @@
-use crate::{sigscan::SigScan, traits::MemError};
+use crate::{
+    sigscan::{
+        chunks::{find_values, value_bytes, ChunkReader},
+        generate::{self, GenerateError},
+        AsSignature, FoundString, SetMatches, SigScan, Signature, SignatureSet,
+    },
+    structures::regions::{MemoryRegion, RegionFilter},
+    traits::MemError,
+};
 
 use super::Module;
 
@@ -6,59 +14,237 @@ impl<T> Module<T>
 where
     T: SigScan,
 {
-    #[cfg(windows)]
-    /// scan for a pattern in the module
-    pub fn scan(&self, pattern: &str) -> Result<Option<usize>, MemError> {
-        use windows::Win32::System::Memory::{MEM_COMMIT, PAGE_NOACCESS};
-        let mut addr = self.get_base_address();
-        let owner = self.get_owner();
-        // this should have no performance implications cause get_base_address.. is const
-        while addr < self.get_base_address() + self.get_size() {
-            unsafe {
-                let query = owner.raw_query(addr);
-                if query.State != MEM_COMMIT || query.Protect == PAGE_NOACCESS {
-                    addr += query.RegionSize;
-                    continue;
-                }
-                let mut page = [0u8; WIN_PAGE_SIZE];
-                owner.raw_read(addr, &mut page as *mut u8, WIN_PAGE_SIZE)?;
-                let scan_res = owner.scan(pattern, page.iter());
-
-                if let Some(result) = scan_res {
-                    println!("Found pattern at {:#x}", scan_res.unwrap());
-                    return Ok(Some(addr + result));
-                }
-                addr += WIN_PAGE_SIZE;
-            }
-        }
-        Ok(None)
+    /// get the mapped regions of the module, clipped to the bounds of the module
+    pub fn regions(&self) -> Result<impl Iterator<Item = MemoryRegion> + '_, MemError> {
+        let (base, end) = (self.get_base_address(), self.get_end_address());
+        Ok(self
+            .get_owner()
+            .regions()?
+            .filter(move |region| region.start < end && base < region.end)
+            .map(move |mut region| {
+                region.start = region.start.max(base);
+                region.end = region.end.min(end);
+                region
+            }))
+    }
+    /// a filter which selects the readable parts of the module
+    pub fn region_filter(&self) -> RegionFilter {
+        RegionFilter::new().range(self.get_base_address()..self.get_end_address())
+    }
+    /// scan for a pattern string or [`Signature`](crate::sigscan::Signature) in the module
+    pub fn scan(&self, pattern: impl AsSignature) -> Result<Option<usize>, MemError> {
+        self.get_owner().scan_regions(pattern, self.region_filter())
+    }
+    /// scan for every match of a pattern string or [`Signature`](crate::sigscan::Signature) in the module.
+    /// useful to check that a signature is unique.
+    pub fn scan_all(
+        &self,
+        pattern: impl AsSignature,
+    ) -> Result<impl Iterator<Item = Result<usize, MemError>> + '_, MemError> {
+        self.get_owner()
+            .scan_regions_all(pattern, self.region_filter())
+    }
+    /// scan for a pattern string or [`Signature`](crate::sigscan::Signature) in the module across a worker thread
+    /// per core, see [`SigScan::par_scan_regions`]
+    pub fn par_scan(&self, pattern: impl AsSignature) -> Result<Option<usize>, MemError>
+    where
+        T: Sync,
+    {
+        self.get_owner()
+            .par_scan_regions(pattern, self.region_filter())
     }
-    #[cfg(windows)]
-    /// scan for a value of <V> in the module
+    /// scan for every match of a pattern string or [`Signature`](crate::sigscan::Signature) in the module across a
+    /// worker thread per core, the matches are in address order
+    pub fn par_scan_all(&self, pattern: impl AsSignature) -> Result<Vec<usize>, MemError>
+    where
+        T: Sync,
+    {
+        self.get_owner()
+            .par_scan_regions_all(pattern, self.region_filter())
+    }
+    /// list the printable strings of at least <min_len> characters in the module, see [`SigScan::strings`]
+    pub fn strings(
+        &self,
+        min_len: usize,
+    ) -> Result<impl Iterator<Item = Result<FoundString, MemError>> + '_, MemError> {
+        self.get_owner().strings(self.region_filter(), min_len)
+    }
+    /// scan for every signature in <set> in the module at once, reading the module only once
+    pub fn scan_set(&self, set: &SignatureSet) -> Result<SetMatches, MemError> {
+        self.get_owner().scan_set(set, self.region_filter())
+    }
+    /// generate the shortest signature which only matches <addr> in the module, at most
+    /// [`Signature::MAX_GENERATED`](crate::sigscan::Signature::MAX_GENERATED) bytes long.
+    /// bytes which look like pointers into the module, relative branches or RIP relative displacements are
+    /// wildcarded, so the signature is more likely to survive updates.
+    pub fn generate_signature(&self, addr: usize) -> Result<Signature, GenerateError> {
+        generate::generate(
+            self.get_owner(),
+            self.get_base_address()..self.get_end_address(),
+            addr,
+            |signature| self.scan_all(signature)?.collect(),
+        )
+    }
+    /// scan for a value of <V> in the module, only addresses which are a multiple of the size of <V> are checked
     pub fn scan_value<V>(&self, val: &V) -> Result<Option<usize>, MemError> {
-        use windows::Win32::System::Memory::{MEM_COMMIT, PAGE_NOACCESS};
-        let mut addr = self.get_base_address();
+        self.scan_value_all(val)?.next().transpose()
+    }
+    /// scan for every instance of a value of <V> in the module, only addresses which are a multiple of the size of
+    /// <V> are checked
+    pub fn scan_value_all<'a, V>(
+        &'a self,
+        val: &'a V,
+    ) -> Result<impl Iterator<Item = Result<usize, MemError>> + 'a, MemError> {
+        let bytes = value_bytes(val);
+        let align = std::mem::size_of::<V>().max(1);
+        let overlap = bytes.len().saturating_sub(1);
+        Ok(
+            ChunkReader::filtered(self.get_owner(), &self.region_filter(), overlap)?
+                .find_all(move |addr, data| find_values(&bytes, align, addr, data)),
+        )
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use crate::structures::process::{implement::utils::ProcessUtils, Process};
+
+    static NEEDLE: [u8; 12] = [
+        0xB7, 0x0C, 0x5E, 0x13, 0x9A, 0xF2, 0x44, 0xD1, 0x6E, 0x29, 0x83, 0xC5,
+    ];
+    static NEEDLE_VALUE: u64 = 0x8E3F_51C2_77A9_0D64;
+
+    #[test]
+    fn test_scan_internal() {
+        let module = Process::this_process().get_base_module().unwrap();
+        let found = module
+            .scan("B7 0C 5E 13 9A ? 44 D1 6E 29 83 C5")
+            .unwrap()
+            .unwrap();
+        assert_eq!(found, NEEDLE.as_ptr() as usize);
+        let found = module.scan_value(&NEEDLE_VALUE).unwrap().unwrap();
+        assert_eq!(found, &NEEDLE_VALUE as *const u64 as usize);
+    }
+    #[test]
+    fn test_scan_external() {
+        let module = Process::find_pid(std::process::id())
+            .unwrap()
+            .get_base_module()
+            .unwrap();
+        let found = module
+            .scan("B7 0C 5E 13 9A F2 44 D1 6E 29 ? C5")
+            .unwrap()
+            .unwrap();
+        assert_eq!(found, NEEDLE.as_ptr() as usize);
+        let found = module.scan_value(&NEEDLE_VALUE).unwrap().unwrap();
+        assert_eq!(found, &NEEDLE_VALUE as *const u64 as usize);
+        assert!(module
+            .scan("B7 0C 5E 13 9A F2 44 D1 6E 29 83 C6")
+            .unwrap()
+            .is_none());
+    }
+    #[test]
+    fn test_scan_all() {
+        static REPEATED: [u64; 3] = [NEEDLE_VALUE ^ 1, 0, NEEDLE_VALUE ^ 1];
+        let module = Process::this_process().get_base_module().unwrap();
+        let found = module
+            .scan_all("B7 0C 5E 13 9A F2 44 D1 6E 29 83 C5")
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(found, [NEEDLE.as_ptr() as usize]);
+        let found = module
+            .scan_value_all(&REPEATED[0])
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            found,
+            [
+                &REPEATED[0] as *const u64 as usize,
+                &REPEATED[2] as *const u64 as usize
+            ]
+        );
+    }
+    #[test]
+    fn test_scan_value_stride() {
+        #[repr(C, align(16))]
+        struct Values([u32; 6]);
+        // the pair is at offset 0 and at offset 12, which is aligned for a [u32; 2] but not a multiple of its size
+        static VALUES: Values = Values([0x5C1E_93A7, 0x0B62_F4D8, 0, 0x5C1E_93A7, 0x0B62_F4D8, 0]);
+        let pair: &[u32; 2] = VALUES.0[..2].try_into().unwrap();
+        let module = Process::this_process().get_base_module().unwrap();
+        let found = module
+            .scan_value_all(pair)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(found, [VALUES.0.as_ptr() as usize]);
+    }
+    #[test]
+    fn test_scan_across_boundaries() {
+        use crate::{sigscan::chunks::CHUNK_SIZE, testing::Mapping, traits::Mem};
+
+        let map = Mapping::new(CHUNK_SIZE + 0x1000);
+        // straddles the first page, which becomes its own region, and the rest of the mapping
+        map.write(0x1000 - 6, &NEEDLE);
+        // straddles the first and second chunk
+        map.write(CHUNK_SIZE - 6, &NEEDLE);
+        map.protect(0, 0x1000, libc::PROT_READ);
+        let page = map.start();
+        let module = map.module("mapping");
+        assert_eq!(
+            module.get_owner().query(page).unwrap().get_end(),
+            page + 0x1000
+        );
 
-        while addr < self.get_base_address() + self.get_size() {
-            unsafe {
-                let query = self.get_owner().raw_query(addr);
-                if query.State != MEM_COMMIT || query.Protect == PAGE_NOACCESS {
-                    addr += query.RegionSize;
-                    continue;
-                }
-                let mut page = [0u8; WIN_PAGE_SIZE];
-                self.get_owner()
-                    .raw_read(addr, &mut page as *mut u8, WIN_PAGE_SIZE)?;
-                let scan_res = self.get_owner().scan_batch_value(val, &page);
+        let found = module
+            .scan_all("B7 0C 5E 13 9A F2 ? D1 6E 29 83 C5")
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(found, [page + 0x1000 - 6, page + CHUNK_SIZE - 6]);
+        assert_eq!(
+            module
+                .par_scan_all("B7 0C 5E 13 9A F2 ? D1 6E 29 83 C5")
+                .unwrap(),
+            found
+        );
+        assert_eq!(
+            module.scan("B7 0C 5E 13 9A F2 44 D1 6E 29 83 C5").unwrap(),
+            Some(page + 0x1000 - 6)
+        );
+    }
+    #[test]
+    fn test_generate_signature() {
+        use crate::sigscan::GenerateError;
 
-                if let Some(result) = scan_res {
-                    println!("Found pattern at {:#x}", scan_res.unwrap());
-                    return Ok(Some(addr + result));
-                }
-                addr += WIN_PAGE_SIZE;
-            }
+        #[inline(never)]
+        fn marker(x: u64) -> u64 {
+            x.wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ 0x1234_5678_9ABC_DEF1
         }
-        Ok(None)
+        std::hint::black_box(marker(1));
+        let module = Process::this_process().get_base_module().unwrap();
+
+        let addr = marker as *const () as usize;
+        let signature = module.generate_signature(addr).unwrap();
+        assert!(signature.len() <= 32, "{signature}");
+        let found = module
+            .scan_all(&signature)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(found, [addr]);
+
+        let signature = module.generate_signature(NEEDLE.as_ptr() as usize).unwrap();
+        assert!(signature.len() <= NEEDLE.len(), "{signature}");
+        assert_eq!(
+            module.scan(&signature).unwrap(),
+            Some(NEEDLE.as_ptr() as usize)
+        );
+        assert!(matches!(
+            module.generate_signature(module.get_end_address()),
+            Err(GenerateError::OutOfModule(_))
+        ));
     }
 }
-pub(super) const WIN_PAGE_SIZE: usize = 0x1000;
@@ -11,6 +11,7 @@ pub struct Module<T: SigScan> {
     pub(crate) base_address: usize,
     pub(crate) end_address: usize,
     pub(crate) size: usize,
+    #[cfg_attr(not(windows), allow(dead_code))]
     pub(crate) handle: isize,
     pub(crate) owner: Arc<T>,
 }
@@ -60,6 +61,9 @@ pub enum ModuleError {
     /// The module was not found in the process.
     #[error("'{0}' was not found in the process")]
     NoModuleFound(String),
+    /// The modules of the process could not be enumerated.
+    #[error("unable to enumerate the modules of the process")]
+    UnableToEnumerate,
     /// The module handle could not be retrieved.
     #[error("unable to open handle for '{0}'")]
     UnableToOpenHandle(String),
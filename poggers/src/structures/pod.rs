@@ -0,0 +1,83 @@
+use super::addr::Pointer;
+
+/// Plain old data, a type for which every combination of bytes is a valid value, so it can be read from another
+/// process without checking what was read. used by [`Mem::read_pod`](crate::traits::Mem::read_pod) and the other
+/// typed reads and writes.
+/// implemented for the integers, floats, [`Pointer`] and arrays of them, and derivable with `#[derive(Pod)]` from
+/// `poggers-derive` for a `#[repr(C)]` structure of pod fields without padding.
+/// ```ignore
+/// #[derive(poggers_derive::Pod, Clone, Copy)]
+/// #[repr(C)]
+/// struct Vec3 {
+///     x: f32,
+///     y: f32,
+///     z: f32,
+/// }
+/// let position = unsafe { process.read_pod::<Vec3>(0x12345678)? };
+/// ```
+/// # Safety
+/// every bit pattern has to be a valid value of the type, which rules out `bool`, `char`, enums, references and
+/// `NonZero*`, and the type may not have padding, which would be written to the process uninitialized.
+pub unsafe trait Pod: Copy {}
+
+macro_rules! impl_pod {
+    ($($ty:ty),*) => {
+        $(unsafe impl Pod for $ty {})*
+    };
+}
+
+impl_pod!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+unsafe impl<T> Pod for Pointer<T> {}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use poggers_derive::Pod;
+
+    use crate::{
+        structures::{addr::Pointer, process::Process},
+        traits::Mem,
+    };
+
+    #[derive(Pod, Debug, Clone, Copy, PartialEq)]
+    #[repr(C)]
+    struct Player {
+        id: u32,
+        health: f32,
+        position: [f32; 3],
+        flags: [u8; 4],
+        target: Pointer<Player>,
+    }
+
+    #[test]
+    fn test_pod() {
+        let mut players = [Player {
+            id: 1,
+            health: 100.0,
+            position: [1.0, 2.0, 3.0],
+            flags: [1, 0, 0, 1],
+            target: unsafe { std::mem::transmute::<usize, Pointer<Player>>(0x1337) },
+        }; 3];
+        players[2].id = 3;
+        let addr = players.as_ptr() as usize;
+        let ex = Process::find_pid(std::process::id()).unwrap();
+        unsafe {
+            let first = ex.read_pod::<Player>(addr).unwrap();
+            assert_eq!(first, players[0]);
+            assert_eq!(first.target.get_address(), 0x1337);
+
+            let target = ex.read_vec::<u32>(addr + 0x18, 2).unwrap();
+            assert_eq!(target, [0x1337, 0]);
+            let all = ex.read_vec::<Player>(addr, 3).unwrap();
+            assert_eq!(all, players);
+
+            let mut buf = [0f32; 2];
+            ex.read_into(addr + 4, &mut buf).unwrap();
+            assert_eq!(buf, [100.0, 1.0]);
+
+            ex.write_pod(addr + 0x24, &50f32).unwrap();
+        }
+        assert_eq!(std::hint::black_box(&mut players)[1].health, 50.0);
+    }
+}
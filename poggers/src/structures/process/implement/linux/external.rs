@@ -1,15 +1,41 @@
+use std::{io::Read, sync::Arc};
+
 use libc::{__errno_location, c_void, process_vm_readv, process_vm_writev};
-use tracing::instrument;
+use tracing::{instrument, warn};
+
+use super::maps;
 
 use crate::{
     sigscan::SigScan,
     structures::{
+        modules::{Module, ModuleError},
         process::{implement::utils::ProcessUtils, External, Process, ProcessError, U32OrString},
         protections::Protections,
+        regions::MemoryRegion,
     },
-    traits::Mem,
+    traits::{Mem, MemError},
 };
 
+/// `EI_CLASS` of a 32 bit ELF file
+const ELFCLASS32: u8 = 1;
+
+/// the most iovecs process_vm_readv accepts in one call
+const IOV_MAX: usize = 1024;
+
+/// checks the result of a process_vm_readv / process_vm_writev call which was expected to transfer <size> bytes.
+/// on failure returns the amount of bytes transferred and the errno.
+/// a short transfer means the remote span ran into memory which could not be accessed, so it is reported as EFAULT.
+unsafe fn check_transfer(res: isize, size: usize) -> Result<(), (usize, i32)> {
+    if res < 0 {
+        return Err((0, __errno_location().read()));
+    }
+    let transferred = res as usize;
+    if transferred < size {
+        return Err((transferred, libc::EFAULT));
+    }
+    Ok(())
+}
+
 impl Mem for Process<External> {
     /// will always return unsupported.
     #[inline]
@@ -43,12 +69,64 @@ impl Mem for Process<External> {
             iov_len: size,
         }];
 
-        let res = process_vm_readv(self.pid as i32, local.as_ptr(), 2, remote.as_ptr(), 1, 0);
-        // TODO @pozm:  handle errors;
-        println!("res: {}", res);
-        let errno = __errno_location().read();
-        println!("errno: {}", errno);
-        Ok(())
+        let res = process_vm_readv(self.pid as i32, local.as_ptr(), 1, remote.as_ptr(), 1, 0);
+        check_transfer(res, size).map_err(|(read, errno)| MemError::ReadFailure(addr, read, errno))
+    }
+
+    /// reads up to 1024 entries per process_vm_readv call. the kernel stops at the first entry which can not be read,
+    /// so that entry fails and the call is repeated for the entries after it.
+    unsafe fn read_batch(&self, entries: &mut [(usize, &mut [u8])]) -> Vec<Result<(), MemError>> {
+        let mut results = Vec::with_capacity(entries.len());
+        while results.len() < entries.len() {
+            let done = results.len();
+            let end = (done + IOV_MAX).min(entries.len());
+            let pending = &mut entries[done..end];
+            let (local, remote): (Vec<_>, Vec<_>) = pending
+                .iter_mut()
+                .map(|(addr, buf)| {
+                    let local = libc::iovec {
+                        iov_base: buf.as_mut_ptr() as *mut c_void,
+                        iov_len: buf.len(),
+                    };
+                    let remote = libc::iovec {
+                        iov_base: *addr as *mut c_void,
+                        iov_len: buf.len(),
+                    };
+                    (local, remote)
+                })
+                .unzip();
+            let res = process_vm_readv(
+                self.pid as i32,
+                local.as_ptr(),
+                local.len() as libc::c_ulong,
+                remote.as_ptr(),
+                remote.len() as libc::c_ulong,
+                0,
+            );
+            if res < 0 {
+                let errno = __errno_location().read();
+                // a fault is the first entry being unreadable, anything else would fail every entry the same way
+                if errno != libc::EFAULT {
+                    let failed = entries[done..]
+                        .iter()
+                        .map(|(addr, _)| MemError::ReadFailure(*addr, 0, errno));
+                    results.extend(failed.map(Err));
+                    break;
+                }
+                results.push(Err(MemError::ReadFailure(pending[0].0, 0, errno)));
+                continue;
+            }
+            let mut read = res as usize;
+            for (addr, buf) in pending.iter() {
+                if read < buf.len() {
+                    results.push(Err(MemError::ReadFailure(*addr, read, libc::EFAULT)));
+                    break;
+                }
+                read -= buf.len();
+                results.push(Ok(()));
+            }
+        }
+        results
     }
 
     unsafe fn raw_write(
@@ -66,10 +144,16 @@ impl Mem for Process<External> {
             iov_len: size,
         }];
 
-        let res = process_vm_writev(self.pid as i32, local.as_ptr(), 2, remote.as_ptr(), 1, 0);
-        // TODO @pozm:  handle errors;
-
-        Ok(())
+        let res = process_vm_writev(self.pid as i32, local.as_ptr(), 1, remote.as_ptr(), 1, 0);
+        check_transfer(res, size)
+            .map_err(|(written, errno)| MemError::WriteFailure(addr, written, errno))
+    }
+    fn regions(&self) -> Result<impl Iterator<Item = MemoryRegion>, MemError> {
+        maps::read_maps(self.pid)
+    }
+    /// detected from the ELF header when the process was opened
+    fn pointer_width(&self) -> usize {
+        self.width
     }
     /// will always return unsupported.
     #[inline]
@@ -119,9 +203,10 @@ impl Process<External> {
                 continue;
             }
             let path = file.path().join("comm");
-            let f = std::fs::read_to_string(path).map_err(|_| {
-                ProcessError::UnableToFindProcess(U32OrString::String(name.to_string()))
-            })?;
+            // the process may have exited since the directory was read
+            let Ok(f) = std::fs::read_to_string(path) else {
+                continue;
+            };
             if f.trim() == name {
                 let pid = file.file_name().to_str().unwrap().parse::<u32>().unwrap();
                 return Self::new(pid);
@@ -143,13 +228,30 @@ impl Process<External> {
 
     #[instrument]
     fn new(pid: u32) -> Result<Self, crate::structures::process::ProcessError> {
-        let name = std::fs::read_to_string(format!("/proc/{}/comm", pid))
+        std::fs::metadata(format!("/proc/{}/comm", pid))
             .map_err(|_| ProcessError::UnableToFindProcess(U32OrString::U32(pid)))?;
         Ok(Self {
             pid,
+            width: Self::detect_pointer_width(pid),
             mrk: std::marker::PhantomData,
         })
     }
+    /// reads the class from the ELF header of `/proc/<pid>/exe`, 32 bit executables have 4 byte pointers.
+    /// the executable can only be read with the same permissions as the memory, otherwise this process's width is used
+    fn detect_pointer_width(pid: u32) -> usize {
+        let mut header = [0u8; 5];
+        let class = std::fs::File::open(format!("/proc/{}/exe", pid))
+            .and_then(|mut exe| exe.read_exact(&mut header))
+            .map(|_| header[4]);
+        match class {
+            Ok(ELFCLASS32) => 4,
+            Ok(_) => std::mem::size_of::<usize>(),
+            Err(e) => {
+                warn!("unable to read the ELF header of {pid} ({e}), assuming the pointer width of this process");
+                std::mem::size_of::<usize>()
+            }
+        }
+    }
 }
 impl ProcessUtils for Process<External> {
     #[instrument]
@@ -157,13 +259,58 @@ impl ProcessUtils for Process<External> {
         std::fs::read_to_string(format!("/proc/{}/comm", self.pid)).unwrap()
     }
     #[instrument]
-    fn get_module(
-        &self,
-        name: &str,
-    ) -> Result<crate::structures::modules::Module<Self>, crate::structures::modules::ModuleError>
+    fn get_module(&self, name: &str) -> Result<Module<Self>, ModuleError>
+    where
+        Self: Sized + SigScan,
+    {
+        self.modules()?
+            .find(|module| module.get_name() == name)
+            .ok_or(ModuleError::NoModuleFound(name.to_string()))
+    }
+    /// resolves the base module from `/proc/<pid>/exe`, as the name in `comm` is truncated to 15 characters
+    #[instrument]
+    fn get_base_module(&self) -> Result<Module<Self>, ModuleError>
+    where
+        Self: Sized + SigScan,
+    {
+        let exe = std::fs::read_link(format!("/proc/{}/exe", self.pid))
+            .map_err(|_| ModuleError::UnableToOpenHandle(format!("/proc/{}/exe", self.pid)))?;
+        self.modules()?
+            .find(|module| module.get_path() == exe)
+            .ok_or(ModuleError::NoModuleFound(
+                exe.to_string_lossy().to_string(),
+            ))
+    }
+    /// builds the modules from the file backed mappings of the process
+    fn modules(&self) -> Result<impl Iterator<Item = Module<Self>>, ModuleError>
     where
         Self: Sized + SigScan,
     {
-        todo!()
+        let files = maps::mapped_files(self.pid).map_err(|_| ModuleError::UnableToEnumerate)?;
+        let owner = Arc::new(self.clone());
+        Ok(files.into_iter().map(move |file| Module {
+            name: Arc::from(
+                file.path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .as_ref(),
+            ),
+            base_address: file.start,
+            end_address: file.end,
+            size: file.end - file.start,
+            path: file.path,
+            handle: 0,
+            owner: owner.clone(),
+        }))
+    }
+}
+impl Clone for Process<External> {
+    fn clone(&self) -> Self {
+        Self {
+            pid: self.pid,
+            width: self.width,
+            mrk: std::marker::PhantomData,
+        }
     }
 }
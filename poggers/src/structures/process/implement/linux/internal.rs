@@ -1,12 +1,22 @@
+use std::{
+    ffi::{CStr, OsStr},
+    os::unix::ffi::OsStrExt,
+    path::Path,
+    sync::Arc,
+};
+
+use libc::{c_int, c_void};
 use tracing::instrument;
 
 use crate::{
     sigscan::SigScan,
     structures::{
-        process::{implement::utils::ProcessUtils, External, Internal, Process},
+        modules::{Module, ModuleError},
+        process::{implement::utils::ProcessUtils, Internal, Process},
         protections::Protections,
+        regions::MemoryRegion,
     },
-    traits::Mem,
+    traits::{Mem, MemError},
 };
 
 impl Mem for Process<Internal> {
@@ -30,7 +40,8 @@ impl Mem for Process<Internal> {
         data: *mut u8,
         size: usize,
     ) -> Result<(), crate::traits::MemError> {
-        (addr as *mut u8).copy_to_nonoverlapping(data, size);
+        // a scan of the whole process also reads the buffer it is reading into
+        (addr as *mut u8).copy_to(data, size);
         Ok(())
     }
 
@@ -44,6 +55,10 @@ impl Mem for Process<Internal> {
         Ok(())
     }
 
+    fn regions(&self) -> Result<impl Iterator<Item = MemoryRegion>, MemError> {
+        super::maps::read_maps(self.pid)
+    }
+
     unsafe fn raw_virtual_alloc(
         &self,
         addr: Option<usize>,
@@ -80,9 +95,9 @@ impl Mem for Process<Internal> {
 }
 impl Process<Internal> {
     pub(crate) fn new() -> Self {
-        let name = std::fs::read_to_string("/proc/self/comm").unwrap();
         Self {
             pid: unsafe { libc::getpid() } as u32,
+            width: std::mem::size_of::<usize>(),
             mrk: Default::default(),
         }
     }
@@ -94,13 +109,110 @@ impl ProcessUtils for Process<Internal> {
         std::fs::read_to_string("/proc/self/comm").unwrap()
     }
     #[instrument]
-    fn get_module(
-        &self,
-        name: &str,
-    ) -> Result<crate::structures::modules::Module<Self>, crate::structures::modules::ModuleError>
+    fn get_module(&self, name: &str) -> Result<Module<Self>, ModuleError>
     where
         Self: Sized + SigScan,
     {
-        todo!()
+        self.modules()?
+            .find(|module| module.get_name() == name)
+            .ok_or(ModuleError::NoModuleFound(name.to_string()))
     }
+    /// resolves the base module from the main executable, as the name in `comm` is truncated to 15 characters
+    #[instrument]
+    fn get_base_module(&self) -> Result<Module<Self>, ModuleError>
+    where
+        Self: Sized + SigScan,
+    {
+        let exe = std::env::current_exe()
+            .map_err(|_| ModuleError::UnableToOpenHandle("/proc/self/exe".to_string()))?;
+        self.modules()?
+            .find(|module| module.get_path() == exe)
+            .ok_or(ModuleError::NoModuleFound(
+                exe.to_string_lossy().to_string(),
+            ))
+    }
+    /// enumerates the loaded objects through the dynamic linker (`dl_iterate_phdr`)
+    fn modules(&self) -> Result<impl Iterator<Item = Module<Self>>, ModuleError>
+    where
+        Self: Sized + SigScan,
+    {
+        let mut objects: Vec<LoadedObject> = Vec::new();
+        unsafe {
+            libc::dl_iterate_phdr(
+                Some(collect_object),
+                &mut objects as *mut Vec<LoadedObject> as *mut c_void,
+            )
+        };
+        let owner = Arc::new(self.clone());
+        Ok(objects.into_iter().map(move |object| Module {
+            name: Arc::from(
+                object
+                    .path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .as_ref(),
+            ),
+            path: object.path,
+            base_address: object.start,
+            end_address: object.end,
+            size: object.end - object.start,
+            handle: 0,
+            owner: owner.clone(),
+        }))
+    }
+}
+impl Clone for Process<Internal> {
+    fn clone(&self) -> Self {
+        Self {
+            pid: self.pid,
+            width: self.width,
+            mrk: Default::default(),
+        }
+    }
+}
+
+/// an object loaded by the dynamic linker
+struct LoadedObject {
+    path: Arc<Path>,
+    start: usize,
+    end: usize,
+}
+
+/// callback for `dl_iterate_phdr`, <data> is a `Vec<LoadedObject>`
+unsafe extern "C" fn collect_object(
+    info: *mut libc::dl_phdr_info,
+    _size: usize,
+    data: *mut c_void,
+) -> c_int {
+    let objects = &mut *(data as *mut Vec<LoadedObject>);
+    let info = &*info;
+    let headers = std::slice::from_raw_parts(info.dlpi_phdr, info.dlpi_phnum as usize);
+    let (start, end) = headers
+        .iter()
+        .filter(|header| header.p_type == libc::PT_LOAD)
+        .fold((usize::MAX, 0), |(start, end), header| {
+            let seg_start = info.dlpi_addr as usize + header.p_vaddr as usize;
+            let seg_end = seg_start + header.p_memsz as usize;
+            (start.min(seg_start), end.max(seg_end))
+        });
+    if start >= end {
+        return 0;
+    }
+    let name = if info.dlpi_name.is_null() {
+        &[]
+    } else {
+        CStr::from_ptr(info.dlpi_name).to_bytes()
+    };
+    // the main executable has an empty name in the linker's list
+    let path: Arc<Path> = if name.is_empty() {
+        match std::env::current_exe() {
+            Ok(exe) => Arc::from(exe),
+            Err(_) => return 0,
+        }
+    } else {
+        Arc::from(Path::new(OsStr::from_bytes(name)))
+    };
+    objects.push(LoadedObject { path, start, end });
+    0
 }
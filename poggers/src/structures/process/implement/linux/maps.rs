@@ -0,0 +1,158 @@
+use std::{path::Path, sync::Arc};
+
+use crate::{
+    structures::{
+        protections::Protections,
+        regions::{MemoryRegion, RegionKind},
+    },
+    traits::MemError,
+};
+
+/// reads and parses `/proc/<pid>/maps`
+pub(super) fn read_maps(pid: u32) -> Result<std::vec::IntoIter<MemoryRegion>, MemError> {
+    let maps = std::fs::read_to_string(format!("/proc/{}/maps", pid))
+        .map_err(|_| MemError::RegionEnumFailure)?;
+    Ok(maps
+        .lines()
+        .filter_map(parse_line)
+        .collect::<Vec<_>>()
+        .into_iter())
+}
+
+/// a file mapped into the process, spanning from its first mapping to its last
+pub(super) struct MappedFile {
+    pub(super) path: Arc<Path>,
+    pub(super) start: usize,
+    pub(super) end: usize,
+}
+
+/// collects every file mapped into the process, ordered by where they were first mapped
+pub(super) fn mapped_files(pid: u32) -> Result<Vec<MappedFile>, MemError> {
+    let mut files: Vec<MappedFile> = Vec::new();
+    for region in read_maps(pid)? {
+        let Some(path) = region.path else {
+            continue;
+        };
+        match files.iter_mut().find(|file| file.path == path) {
+            Some(file) => {
+                file.start = file.start.min(region.start);
+                file.end = file.end.max(region.end);
+            }
+            None => files.push(MappedFile {
+                path,
+                start: region.start,
+                end: region.end,
+            }),
+        }
+    }
+    Ok(files)
+}
+
+/// parses a single line of `/proc/<pid>/maps`, e.g.
+/// `7f28c1cf0000-7f28c1e46000 r-xp 00026000 fe:00 395379    /usr/lib/x86_64-linux-gnu/libc.so.6`
+fn parse_line(line: &str) -> Option<MemoryRegion> {
+    let mut fields = line.splitn(6, ' ');
+    let (start, end) = fields.next()?.split_once('-')?;
+    let perms = fields.next()?.as_bytes();
+    let offset = fields.next()?;
+    // device & inode
+    fields.next()?;
+    fields.next()?;
+    let name = fields.next().unwrap_or_default().trim_start();
+    // files which were deleted (or replaced) after being mapped are suffixed
+    let (name, deleted) = match name.strip_suffix(" (deleted)") {
+        Some(x) if x.starts_with('/') => (x, true),
+        _ => (name, false),
+    };
+
+    if perms.len() < 4 {
+        return None;
+    }
+    let protections = Protections::new()
+        .with_read(perms[0] == b'r')
+        .with_write(perms[1] == b'w')
+        .with_execute(perms[2] == b'x')
+        .with_none(&perms[..3] == b"---");
+
+    let (path, label) = match name {
+        "" => (None, None),
+        x if x.starts_with('/') => (Some(Arc::from(Path::new(x))), None),
+        x => (None, Some(Arc::from(x))),
+    };
+    let kind = match name {
+        "" => RegionKind::Anonymous,
+        "[heap]" => RegionKind::Heap,
+        x if x.starts_with('/') => RegionKind::File,
+        // named anonymous mappings, see PR_SET_VMA_ANON_NAME
+        x if x.starts_with("[anon:") => RegionKind::Anonymous,
+        x if x.starts_with("[stack") => RegionKind::Stack,
+        _ => RegionKind::Other,
+    };
+
+    Some(MemoryRegion {
+        start: usize::from_str_radix(start, 16).ok()?,
+        end: usize::from_str_radix(end, 16).ok()?,
+        protections,
+        shared: perms[3] == b's',
+        path,
+        offset: usize::from_str_radix(offset, 16).ok()?,
+        label,
+        kind,
+        deleted,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_line;
+    use crate::structures::regions::RegionKind;
+
+    #[test]
+    fn test_parse_file_backed() {
+        let region = parse_line("7f28c1cf0000-7f28c1e46000 r-xp 00026000 fe:00 395379                     /usr/lib/x86_64-linux-gnu/libc.so.6").unwrap();
+        assert_eq!(region.get_start(), 0x7f28c1cf0000);
+        assert_eq!(region.get_end(), 0x7f28c1e46000);
+        assert_eq!(region.get_offset(), 0x26000);
+        assert!(region.get_protections().read());
+        assert!(!region.get_protections().write());
+        assert!(region.get_protections().execute());
+        assert!(!region.is_shared());
+        assert_eq!(
+            region.get_path().unwrap().to_str(),
+            Some("/usr/lib/x86_64-linux-gnu/libc.so.6")
+        );
+        assert_eq!(region.get_label(), None);
+        assert_eq!(region.get_kind(), RegionKind::File);
+        assert!(!region.is_deleted());
+    }
+    #[test]
+    fn test_parse_deleted() {
+        let region = parse_line(
+            "7f28c1cf0000-7f28c1e46000 r-xp 00026000 fe:00 395379 /tmp/libgame.so (deleted)",
+        )
+        .unwrap();
+        assert_eq!(region.get_path().unwrap().to_str(), Some("/tmp/libgame.so"));
+        assert!(region.is_deleted());
+        assert_eq!(region.get_kind(), RegionKind::File);
+    }
+    #[test]
+    fn test_parse_labelled_and_anonymous() {
+        let heap = parse_line(
+            "562170d34000-562170d55000 rw-s 00000000 00:00 0                          [heap]",
+        )
+        .unwrap();
+        assert_eq!(heap.get_label(), Some("[heap]"));
+        assert!(heap.get_path().is_none());
+        assert!(heap.is_shared());
+        assert_eq!(heap.get_kind(), RegionKind::Heap);
+
+        let anon = parse_line("7f28c1e9f000-7f28c1eac000 ---p 00000000 00:00 0 ").unwrap();
+        assert_eq!(anon.get_label(), None);
+        assert!(anon.get_path().is_none());
+        assert!(anon.get_protections().none());
+        assert_eq!(anon.get_kind(), RegionKind::Anonymous);
+
+        let vdso = parse_line("7ffd3a5f3000-7ffd3a5f5000 r-xp 00000000 00:00 0 [vdso]").unwrap();
+        assert_eq!(vdso.get_kind(), RegionKind::Other);
+    }
+}
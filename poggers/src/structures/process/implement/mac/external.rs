@@ -61,7 +61,7 @@ impl Mem for Process<External> {
             &mut sz,
         );
         if ret != KERN_SUCCESS {
-            return Err(crate::traits::MemError::ReadFailure(addr));
+            return Err(crate::traits::MemError::ReadFailure(addr, sz as usize, ret));
         }
         Ok(())
     }
@@ -75,7 +75,7 @@ impl Mem for Process<External> {
         let ret = mach::vm::mach_vm_write(task, addr as u64, data as vm_address_t, size as u32);
 
         if ret != KERN_SUCCESS {
-            return Err(WriteFailure(addr));
+            return Err(WriteFailure(addr, 0, ret));
         }
         Ok(())
     }
@@ -139,6 +139,7 @@ impl Proc for Process<External> {
 
         Ok(Process::<External> {
             pid,
+            width: std::mem::size_of::<usize>(),
             mrk: PhantomData,
         })
     }
@@ -172,6 +173,7 @@ impl Proc for Process<External> {
             )))?;
         Ok(Process::<External> {
             pid: *pid as u32,
+            width: std::mem::size_of::<usize>(),
             mrk: PhantomData,
         })
     }
@@ -17,23 +17,60 @@ pub use win32::*;
 
 #[cfg(test)]
 mod test {
-    fn spawn_test_process() -> std::process::Child {
+    /// path to rw-test, building it first if needed
+    fn test_binary() -> PathBuf {
         use std::process::Command;
+        // make sure rw-test has been built before trying to spawn it
+        static BUILD: Once = Once::new();
+        BUILD.call_once(|| {
+            let status = Command::new(env!("CARGO"))
+                .args(["build", "--release", "-p", "rw-test"])
+                .current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/.."))
+                .status()
+                .unwrap();
+            assert!(status.success(), "failed to build rw-test");
+        });
         #[cfg(windows)]
-        let proc = Command::new("./target/release/rw-test.exe")
-            .stdout(Stdio::piped())
-            .spawn()
-            .unwrap();
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../target/release/rw-test.exe");
         #[cfg(unix)]
-        let proc = Command::new("./target/release/rw-test")
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../target/release/rw-test");
+        PathBuf::from(path)
+    }
+    fn spawn(path: &Path) -> std::process::Child {
+        std::process::Command::new(path)
             .stdout(Stdio::piped())
             .spawn()
-            .unwrap();
-        proc
+            .unwrap()
+    }
+    fn spawn_test_process() -> std::process::Child {
+        spawn(&test_binary())
+    }
+    /// a copy of rw-test running under its own name, killed and deleted when dropped, even if the test panics
+    struct RenamedCopy {
+        path: PathBuf,
+        proc: std::process::Child,
+    }
+    impl RenamedCopy {
+        fn spawn(name: &str) -> Self {
+            let path = std::env::temp_dir().join(name);
+            std::fs::copy(test_binary(), &path).unwrap();
+            let proc = spawn(&path);
+            Self { path, proc }
+        }
+    }
+    impl Drop for RenamedCopy {
+        fn drop(&mut self) {
+            // windows can not delete the executable while it is running
+            self.proc.kill().ok();
+            self.proc.wait().ok();
+            std::fs::remove_file(&self.path).ok();
+        }
     }
     use std::{
         io::{BufRead, BufReader},
+        path::{Path, PathBuf},
         process::Stdio,
+        sync::Once,
     };
 
     use crate::{structures::process::Process, traits::Mem};
@@ -68,19 +105,27 @@ mod test {
         assert_eq!(val, bufr.trim().parse().unwrap());
 
         proc.kill().unwrap();
+        proc.wait().unwrap();
     }
     #[test]
     fn test_name_lookup() {
-        let mut proc = spawn_test_process();
-        let mut reader = BufReader::new(proc.stdout.take().unwrap());
-        let mut bufr = String::new();
+        // a copy under its own name, so the lookup can not find a rw-test spawned by another test
+        // or by a concurrent test run. kept within the 15 characters linux keeps of a process name
         #[cfg(windows)]
-        let ex = Process::find_name("rw-test.exe").unwrap();
+        let name = format!("rw-test-{}.exe", std::process::id());
         #[cfg(unix)]
-        let ex = Process::find_by_name("rw-test").unwrap();
-
+        let name = format!("rw-test-{}", std::process::id());
+        let mut copy = RenamedCopy::spawn(&name);
+        let mut reader = BufReader::new(copy.proc.stdout.take().unwrap());
+        let mut bufr = String::new();
+        // once it has printed it is running under its own name
         reader.read_line(&mut bufr).ok();
 
+        #[cfg(windows)]
+        let ex = Process::find_name(&name).unwrap();
+        #[cfg(unix)]
+        let ex = Process::find_by_name(&name).unwrap();
+
         let addr = usize::from_str_radix(bufr.trim_start_matches("0x").trim(), 16).unwrap();
 
         let val = unsafe { ex.read::<u32>(addr).unwrap() };
@@ -90,8 +135,6 @@ mod test {
         reader.read_line(&mut bufr).ok();
 
         assert_eq!(val, bufr.trim().parse().unwrap());
-
-        proc.kill().unwrap();
     }
     #[test]
     fn test_writing() {
@@ -114,5 +157,88 @@ mod test {
         assert_eq!(4141656, bufr.trim().parse().unwrap());
 
         proc.kill().unwrap();
+        proc.wait().unwrap();
+    }
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_read_failure() {
+        use crate::traits::MemError;
+
+        let ex = Process::find_pid(std::process::id()).unwrap();
+        let res = unsafe { ex.read::<u32>(0) };
+        assert!(matches!(
+            res,
+            Err(MemError::ReadFailure(0, 0, libc::EFAULT))
+        ));
+    }
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_read_partial() {
+        // map two pages and unmap the second one so the span runs into unmapped memory
+        let mut map = crate::testing::Mapping::new(0x2000);
+        map.unmap_end(0x1000);
+        map.values::<u8>().fill(0x41);
+        let page = map.start();
+        let ex = map.process();
+        let mut buf = [0u8; 0x2000];
+        let readable = unsafe { ex.read_partial(page + 0x800, &mut buf).unwrap() };
+        assert_eq!(readable, 0x800);
+        assert!(buf[..readable].iter().all(|x| *x == 0x41));
+    }
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_regions() {
+        static VALUE: u32 = 0x1337;
+        let addr = &VALUE as *const u32 as usize;
+        let ex = Process::find_pid(std::process::id()).unwrap();
+        let this = Process::this_process();
+
+        let region = ex.query(addr).unwrap();
+        assert!(region.contains(addr));
+        assert!(region.get_protections().read());
+        assert!(region.get_path().is_some());
+        assert_eq!(this.query(addr).unwrap().get_start(), region.get_start());
+
+        let regions = ex.regions().unwrap().collect::<Vec<_>>();
+        assert!(regions
+            .windows(2)
+            .all(|x| x[0].get_end() <= x[1].get_start()));
+        assert!(regions.iter().any(|x| x.get_label() == Some("[stack]")));
+    }
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_external_modules() {
+        use crate::structures::process::implement::utils::ProcessUtils;
+
+        let ex = Process::find_pid(std::process::id()).unwrap();
+        let base = ex.get_base_module().unwrap();
+        let exe = std::env::current_exe().unwrap();
+        assert_eq!(base.get_path(), exe);
+        assert_eq!(base.get_name(), exe.file_name().unwrap().to_str().unwrap());
+        let main_addr = test_external_modules as *const () as usize;
+        assert!(base.get_base_address() <= main_addr && main_addr < base.get_end_address());
+
+        let libc = ex.get_module("libc.so.6").unwrap();
+        assert_eq!(
+            libc.get_size(),
+            libc.get_end_address() - libc.get_base_address()
+        );
+        assert!(ex.get_module("not-a-real-module.so").is_err());
+    }
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_internal_modules() {
+        use crate::structures::process::implement::utils::ProcessUtils;
+
+        let this = Process::this_process();
+        let exe = std::env::current_exe().unwrap();
+        let modules = this.modules().unwrap().collect::<Vec<_>>();
+        assert!(modules.iter().any(|module| module.get_path() == exe));
+        assert!(modules.iter().all(|module| module.get_size() > 0));
+
+        let base = this.get_base_module().unwrap();
+        let main_addr = test_internal_modules as *const () as usize;
+        assert!(base.get_base_address() <= main_addr && main_addr < base.get_end_address());
+        assert!(this.get_module("libc.so.6").is_ok());
     }
 }
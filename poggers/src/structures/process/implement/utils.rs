@@ -7,6 +7,10 @@ use crate::{
 pub trait ProcessUtils {
     /// get a module by name
     fn get_module(&self, name: &str) -> Result<Module<Self>, ModuleError>
+    where
+        Self: Sized + SigScan;
+    /// iterate over every module loaded in the process
+    fn modules(&self) -> Result<impl Iterator<Item = Module<Self>>, ModuleError>
     where
         Self: Sized + SigScan;
     /// get the base module, which is the module with the same name as the process
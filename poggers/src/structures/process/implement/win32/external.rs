@@ -1,11 +1,13 @@
 use std::path::Path;
-use std::{ffi::c_void, marker::PhantomData, mem::size_of, path::PathBuf, sync::Arc};
-use tracing::instrument;
+use std::{ffi::c_void, marker::PhantomData, mem::size_of, sync::Arc};
+use tracing::{instrument, warn};
 use windows::core::PWSTR;
 
-use windows::Win32::System::Threading::{QueryFullProcessImageNameW, PROCESS_NAME_WIN32};
+use windows::Win32::System::Threading::{
+    IsWow64Process, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+};
 use windows::Win32::{
-    Foundation::HANDLE,
+    Foundation::{BOOL, HANDLE, WIN32_ERROR},
     System::{
         Diagnostics::Debug::{ReadProcessMemory, WriteProcessMemory},
         Memory::{
@@ -23,6 +25,7 @@ use crate::{
         modules::{Module, ModuleError},
         process::{External, Process, ProcessError, U32OrString},
         protections::Protections,
+        regions::MemoryRegion,
     },
     traits::{Mem, MemError},
 };
@@ -44,6 +47,16 @@ impl Mem for Process<External> {
         );
         info
     }
+    fn query(&self, addr: usize) -> Result<MemoryRegion, MemError> {
+        super::query_region(HANDLE(self.handl), addr)
+    }
+    fn regions(&self) -> Result<impl Iterator<Item = MemoryRegion>, MemError> {
+        Ok(super::committed_regions(HANDLE(self.handl)))
+    }
+    /// detected with `IsWow64Process` when the process was opened
+    fn pointer_width(&self) -> usize {
+        self.width
+    }
     unsafe fn alter_protection(
         &self,
         addr: usize,
@@ -75,24 +88,26 @@ impl Mem for Process<External> {
         // }
     }
     unsafe fn raw_read(&self, addr: usize, data: *mut u8, size: usize) -> Result<(), MemError> {
+        let mut read = 0;
         ReadProcessMemory(
             HANDLE(self.handl),
             addr as *const c_void,
             data as *mut _,
             size,
-            Some(&mut 0),
+            Some(&mut read),
         )
-        .map_err(|_| MemError::ReadFailure(addr))
+        .map_err(|e| MemError::ReadFailure(addr, read, win32_code(&e)))
     }
     unsafe fn raw_write(&self, addr: usize, data: *const u8, size: usize) -> Result<(), MemError> {
+        let mut written = 0;
         WriteProcessMemory(
             HANDLE(self.handl),
             addr as *const c_void,
             data as *const _,
             size,
-            Some(&mut 0),
+            Some(&mut written),
         )
-        .map_err(|_| MemError::WriteFailure(addr))
+        .map_err(|e| MemError::WriteFailure(addr, written, win32_code(&e)))
     }
     unsafe fn raw_virtual_alloc(
         &self,
@@ -150,11 +165,21 @@ impl Process<External> {
     /// finds the process from a pid
     pub fn find_by_pid(pid: u32) -> Result<Self, ProcessError> {
         let open_hndl = Self::open_handle(pid)?;
-        Ok(Self {
-            handl: open_hndl.0,
-            pid,
-            mrk: Default::default(),
-        })
+        Ok(Self::from_handle(open_hndl, pid))
+    }
+    /// 32 bit processes run under WOW64 on 64 bit windows
+    fn detect_pointer_width(hndl: HANDLE) -> usize {
+        let mut wow64 = BOOL::default();
+        match unsafe { IsWow64Process(hndl, &mut wow64) } {
+            Ok(()) if wow64.as_bool() => 4,
+            Ok(()) => size_of::<usize>(),
+            Err(e) => {
+                warn!(
+                    "unable to check for WOW64 ({e}), assuming the pointer width of this process"
+                );
+                size_of::<usize>()
+            }
+        }
     }
     /// finds the process from a name
     pub fn find_by_name(name: &str) -> Result<Self, ProcessError> {
@@ -168,10 +193,11 @@ impl Process<External> {
 
     // pub function to allow people to make a process from an existing handle. really not very safe or recommended, but it's here if they're sure they want to use it
     /// get a process from a handle. assumes everything is valid about the handle (things could go very wrong if the permissions on handle is incorrect.)
-    pub const fn from_handle(hnd: HANDLE, pid: u32) -> Self {
+    pub fn from_handle(hnd: HANDLE, pid: u32) -> Self {
         Self {
             handl: hnd.0,
             pid,
+            width: Self::detect_pointer_width(hnd),
             mrk: PhantomData,
         }
     }
@@ -201,6 +227,24 @@ impl ProcessUtils for Process<External> {
         })
     }
     #[instrument]
+    fn modules(&self) -> Result<impl Iterator<Item = Module<Self>>, ModuleError>
+    where
+        Self: Sized + SigScan,
+    {
+        let snapshot =
+            ToolSnapshot::new_module(Some(self.pid)).or(Err(ModuleError::UnableToEnumerate))?;
+        let owner = Arc::new(self.clone());
+        Ok(snapshot.map(move |module| Module {
+            base_address: module.base_address,
+            size: module.size,
+            end_address: module.base_address + module.size,
+            path: Arc::from(Path::new(&module.exe_path)),
+            name: Arc::from(module.name.as_ref()),
+            handle: module.handle.0,
+            owner: owner.clone(),
+        }))
+    }
+    #[instrument]
     fn get_name(&self) -> String {
         Self::get_name_from_hndl(HANDLE(self.handl))
     }
@@ -210,7 +254,13 @@ impl Clone for Process<External> {
         Self {
             handl: self.handl,
             pid: self.pid,
+            width: self.width,
             mrk: PhantomData,
         }
     }
 }
+
+/// the win32 error code wrapped by <e>, or its HRESULT if it does not wrap one
+fn win32_code(e: &windows::core::Error) -> i32 {
+    WIN32_ERROR::from_error(e).map_or(e.code().0, |code| code.0 as i32)
+}
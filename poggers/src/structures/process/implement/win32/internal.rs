@@ -1,9 +1,4 @@
-use std::{
-    ffi::c_void,
-    mem::size_of,
-    path::{Path, PathBuf},
-    sync::Arc,
-};
+use std::{ffi::c_void, mem::size_of, path::Path, sync::Arc};
 
 use windows::{
     core::PCWSTR,
@@ -24,9 +19,11 @@ use windows::{
 use crate::{
     sigscan::SigScan,
     structures::{
+        create_snapshot::ToolSnapshot,
         modules::{Module, ModuleError},
         process::{Internal, Process},
         protections::Protections,
+        regions::MemoryRegion,
     },
     traits::{Mem, MemError},
 };
@@ -47,6 +44,13 @@ impl Mem for Process<Internal> {
         info
     }
 
+    fn query(&self, addr: usize) -> Result<MemoryRegion, MemError> {
+        super::query_region(HANDLE(self.handl), addr)
+    }
+    fn regions(&self) -> Result<impl Iterator<Item = MemoryRegion>, MemError> {
+        Ok(super::committed_regions(HANDLE(self.handl)))
+    }
+
     unsafe fn alter_protection(
         &self,
         addr: usize,
@@ -66,7 +70,8 @@ impl Mem for Process<Internal> {
     }
 
     unsafe fn raw_read(&self, addr: usize, data: *mut u8, size: usize) -> Result<(), MemError> {
-        (addr as *mut u8).copy_to_nonoverlapping(data, size);
+        // a scan of the whole process also reads the buffer it is reading into
+        (addr as *mut u8).copy_to(data, size);
         Ok(())
     }
 
@@ -109,6 +114,7 @@ impl Process<Internal> {
         Self {
             handl,
             pid: proc_id,
+            width: size_of::<usize>(),
             mrk: Default::default(),
         }
     }
@@ -144,6 +150,24 @@ impl ProcessUtils for Process<Internal> {
         })
     }
 
+    fn modules(&self) -> Result<impl Iterator<Item = Module<Self>>, ModuleError>
+    where
+        Self: Sized + SigScan,
+    {
+        let snapshot =
+            ToolSnapshot::new_module(Some(self.pid)).or(Err(ModuleError::UnableToEnumerate))?;
+        let owner = Arc::new(self.clone());
+        Ok(snapshot.map(move |module| Module {
+            base_address: module.base_address,
+            size: module.size,
+            end_address: module.base_address + module.size,
+            path: Arc::from(Path::new(&module.exe_path)),
+            name: Arc::from(module.name.as_ref()),
+            handle: module.handle.0,
+            owner: owner.clone(),
+        }))
+    }
+
     fn get_name(&self) -> String {
         let mut file_name = widestring::U16String::new();
         unsafe { GetProcessImageFileNameW(HANDLE(self.handl), file_name.as_mut_slice()) };
@@ -157,6 +181,7 @@ impl Clone for Process<Internal> {
         Self {
             handl: self.handl,
             pid: self.pid,
+            width: self.width,
             mrk: Default::default(),
         }
     }
@@ -1,8 +1,147 @@
+use std::{
+    ffi::{c_void, OsString},
+    mem::size_of,
+    os::windows::ffi::OsStringExt,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use windows::Win32::{
+    Foundation::{HANDLE, MAX_PATH},
+    System::{
+        Memory::{
+            VirtualQueryEx, MEMORY_BASIC_INFORMATION, MEM_COMMIT, MEM_IMAGE, MEM_MAPPED,
+            PAGE_GUARD, PAGE_NOCACHE, PAGE_PROTECTION_FLAGS, PAGE_WRITECOMBINE,
+        },
+        ProcessStatus::GetMappedFileNameW,
+    },
+};
+
+use crate::{
+    structures::{
+        protections::Protections,
+        regions::{MemoryRegion, RegionKind},
+    },
+    traits::MemError,
+};
+
 /// for external usage
-#[feature(external)]
 pub mod external;
 /// for internal usage
-#[feature(internal)]
 pub mod internal;
 
 pub(super) const WIN_PAGE_SIZE: usize = 0x1000;
+
+/// queries the region containing <addr> in the process behind <handle>.
+/// returns none once <addr> is past the end of the address space.
+unsafe fn query_info(handle: HANDLE, addr: usize) -> Option<MEMORY_BASIC_INFORMATION> {
+    let mut info = MEMORY_BASIC_INFORMATION::default();
+    let written = VirtualQueryEx(
+        handle,
+        Some(addr as *const c_void),
+        &mut info,
+        size_of::<MEMORY_BASIC_INFORMATION>(),
+    );
+    (written != 0).then_some(info)
+}
+
+/// gets the path of the file mapped at <addr>, as a device path (`\Device\HarddiskVolume1\...`)
+fn mapped_file(handle: HANDLE, addr: usize) -> Option<Arc<Path>> {
+    let mut name = [0u16; MAX_PATH as usize];
+    let len = unsafe { GetMappedFileNameW(handle, addr as *const c_void, &mut name) } as usize;
+    (len != 0).then(|| Arc::from(PathBuf::from(OsString::from_wide(&name[..len]))))
+}
+
+/// converts the protection of a region, guard pages fault on the first access so they are not readable
+fn to_protections(protect: PAGE_PROTECTION_FLAGS) -> Protections {
+    if protect.contains(PAGE_GUARD) {
+        return Protections::NoAccess;
+    }
+    // PAGE_NOCACHE and PAGE_WRITECOMBINE only modify the caching of the protection
+    (protect & !(PAGE_NOCACHE | PAGE_WRITECOMBINE)).0.into()
+}
+
+fn to_region(handle: HANDLE, info: &MEMORY_BASIC_INFORMATION) -> MemoryRegion {
+    let start = info.BaseAddress as usize;
+    let path = match info.Type {
+        MEM_IMAGE | MEM_MAPPED => mapped_file(handle, start),
+        _ => None,
+    };
+    MemoryRegion {
+        start,
+        end: start + info.RegionSize,
+        protections: to_protections(info.Protect),
+        shared: info.Type == MEM_MAPPED,
+        kind: match path {
+            Some(_) => RegionKind::File,
+            None => RegionKind::Anonymous,
+        },
+        path,
+        offset: 0,
+        label: None,
+        deleted: false,
+    }
+}
+
+/// queries the committed region containing <addr> in the process behind <handle>
+pub(super) fn query_region(handle: HANDLE, addr: usize) -> Result<MemoryRegion, MemError> {
+    match unsafe { query_info(handle, addr) } {
+        Some(info) if info.State == MEM_COMMIT => Ok(to_region(handle, &info)),
+        _ => Err(MemError::QueryFailure(addr)),
+    }
+}
+
+/// walks every committed region in the process behind <handle>
+pub(super) fn committed_regions(handle: HANDLE) -> impl Iterator<Item = MemoryRegion> {
+    let mut addr = 0usize;
+    std::iter::from_fn(move || loop {
+        let info = unsafe { query_info(handle, addr) }?;
+        addr = (info.BaseAddress as usize).checked_add(info.RegionSize)?;
+        if info.State == MEM_COMMIT {
+            return Some(to_region(handle, &info));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use windows::Win32::{
+        Foundation::HANDLE,
+        System::Memory::{
+            MEMORY_BASIC_INFORMATION, MEM_COMMIT, MEM_PRIVATE, PAGE_GUARD, PAGE_NOACCESS,
+            PAGE_NOCACHE, PAGE_READWRITE,
+        },
+    };
+
+    use super::to_region;
+    use crate::structures::protections::Protections;
+
+    #[test]
+    fn test_region_modifiers() {
+        let info = MEMORY_BASIC_INFORMATION {
+            BaseAddress: 0x10000 as *mut _,
+            RegionSize: 0x1000,
+            State: MEM_COMMIT,
+            Protect: PAGE_READWRITE | PAGE_NOCACHE,
+            Type: MEM_PRIVATE,
+            ..Default::default()
+        };
+        let region = to_region(HANDLE::default(), &info);
+        assert!(matches!(region.get_protections(), Protections::ReadWrite));
+        assert!(region.get_protections().is_readable());
+
+        let guard = MEMORY_BASIC_INFORMATION {
+            Protect: PAGE_READWRITE | PAGE_GUARD,
+            ..info
+        };
+        let region = to_region(HANDLE::default(), &guard);
+        assert!(!region.get_protections().is_readable());
+
+        let no_access = MEMORY_BASIC_INFORMATION {
+            Protect: PAGE_NOACCESS,
+            ..info
+        };
+        let region = to_region(HANDLE::default(), &no_access);
+        assert!(!region.get_protections().is_readable());
+    }
+}
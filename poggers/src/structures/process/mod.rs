@@ -21,6 +21,8 @@ pub struct Process<T = Holding> {
     /// always none on linux, some on windows. is the handle. (to get actual HANDLE, you must wrap
     /// in HANDLE)
     handl: isize,
+    /// the size of a pointer in the process, detected once when it is opened
+    pub(crate) width: usize,
     pub(crate) mrk: PhantomData<T>,
 }
 
@@ -91,6 +93,7 @@ impl TryFrom<&str> for Process<External> {
     }
 }
 impl SigScan for Process<External> {}
+#[allow(dead_code)]
 trait Proc {
     fn find_by_name(name: &str) -> Result<Process<External>, ProcessError>;
     fn find_by_pid(pid: u32) -> Result<Process<External>, ProcessError>;
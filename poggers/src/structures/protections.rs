@@ -1,5 +1,5 @@
 /// Memory Protection Flags
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 #[cfg(windows)]
 pub enum Protections {
     /// If memory can execute in this page ?
@@ -29,14 +29,18 @@ pub enum Protections {
 #[cfg(unix)]
 #[bitfield_struct::bitfield(u8)]
 pub struct Protections {
-    read: bool,
-    write: bool,
-    execute: bool,
-    none: bool,
+    /// memory can be read
+    pub read: bool,
+    /// memory can be written to
+    pub write: bool,
+    /// memory can be executed
+    pub execute: bool,
+    /// no access
+    pub none: bool,
     #[bits(4)]
     __: u8,
 }
-use std::fmt::{Debug, Display};
+use std::fmt::Display;
 #[cfg(windows)]
 use windows::Win32::System::Memory::PAGE_PROTECTION_FLAGS;
 
@@ -62,6 +66,38 @@ impl Protections {
     pub const fn native(&self) -> PAGE_PROTECTION_FLAGS {
         PAGE_PROTECTION_FLAGS(self.u32())
     }
+    /// can memory with these protections be read
+    pub const fn is_readable(&self) -> bool {
+        matches!(
+            self,
+            Protections::ExecuteRead
+                | Protections::ExecuteReadWrite
+                | Protections::ExecuteWriteCopy
+                | Protections::ReadOnly
+                | Protections::ReadWrite
+                | Protections::WriteCopy
+        )
+    }
+    /// can memory with these protections be written to
+    pub const fn is_writable(&self) -> bool {
+        matches!(
+            self,
+            Protections::ExecuteReadWrite
+                | Protections::ExecuteWriteCopy
+                | Protections::ReadWrite
+                | Protections::WriteCopy
+        )
+    }
+    /// can memory with these protections be executed
+    pub const fn is_executable(&self) -> bool {
+        matches!(
+            self,
+            Protections::Execute
+                | Protections::ExecuteRead
+                | Protections::ExecuteReadWrite
+                | Protections::ExecuteWriteCopy
+        )
+    }
 }
 #[cfg(windows)]
 impl From<u32> for Protections {
@@ -128,6 +164,18 @@ impl Protections {
     pub fn native(&self) -> i32 {
         self.u32()
     }
+    /// can memory with these protections be read
+    pub const fn is_readable(&self) -> bool {
+        self.read()
+    }
+    /// can memory with these protections be written to
+    pub const fn is_writable(&self) -> bool {
+        self.write()
+    }
+    /// can memory with these protections be executed
+    pub const fn is_executable(&self) -> bool {
+        self.execute()
+    }
     /// construct protections from native version
     pub fn from_native(prot: i32) -> Self {
         let mut ret = Protections::new();
@@ -0,0 +1,174 @@
+use std::{ops::Range, path::Path, sync::Arc};
+
+use super::protections::Protections;
+
+/// represents a region of mapped memory in a process
+#[derive(Debug, Clone)]
+pub struct MemoryRegion {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) protections: Protections,
+    pub(crate) shared: bool,
+    pub(crate) path: Option<Arc<Path>>,
+    pub(crate) offset: usize,
+    pub(crate) label: Option<Arc<str>>,
+    pub(crate) kind: RegionKind,
+    pub(crate) deleted: bool,
+}
+
+/// what is mapped into a [`MemoryRegion`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegionKind {
+    /// anonymous memory which is not backed by a file, e.g. JIT compiled code or `mmap`ed allocations
+    Anonymous,
+    /// the heap of the process (`[heap]`), only known on linux. heaps on windows are [`RegionKind::Anonymous`]
+    Heap,
+    /// the stack of a thread, only known on linux
+    Stack,
+    /// a mapping backed by a file, such as a module
+    File,
+    /// any other special mapping, such as `[vdso]` or `[vvar]`. only selected by a [`RegionFilter`] which asks for it,
+    /// as some of these can not be read even though they are mapped readable
+    Other,
+}
+
+impl MemoryRegion {
+    /// Get the start address of the region
+    pub const fn get_start(&self) -> usize {
+        self.start
+    }
+    /// Get the end address of the region (exclusive)
+    pub const fn get_end(&self) -> usize {
+        self.end
+    }
+    /// Get the size of the region
+    pub const fn get_size(&self) -> usize {
+        self.end - self.start
+    }
+    /// Get the protections of the region
+    pub const fn get_protections(&self) -> &Protections {
+        &self.protections
+    }
+    /// is the region shared with other processes, otherwise it is private
+    pub const fn is_shared(&self) -> bool {
+        self.shared
+    }
+    /// Get the path of the file backing this region, if any
+    pub fn get_path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+    /// Get the offset into the backing file which this region starts at
+    pub const fn get_offset(&self) -> usize {
+        self.offset
+    }
+    /// Get the label of the region, such as `[heap]` or `[stack]`
+    pub fn get_label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+    /// Get what is mapped into the region
+    pub const fn get_kind(&self) -> RegionKind {
+        self.kind
+    }
+    /// has the file backing this region been deleted since it was mapped, only known on linux
+    pub const fn is_deleted(&self) -> bool {
+        self.deleted
+    }
+    /// check if <addr> lies within this region
+    pub const fn contains(&self, addr: usize) -> bool {
+        self.start <= addr && addr < self.end
+    }
+}
+
+/// selects which regions of a process are scanned, see [`SigScan::scan_regions`](crate::sigscan::SigScan::scan_regions).
+/// an empty filter selects every readable region, except special mappings ([`RegionKind::Other`]).
+/// ```
+/// use poggers::structures::regions::{RegionFilter, RegionKind};
+/// // JIT compiled code
+/// let jit = RegionFilter::new().executable().kind(RegionKind::Anonymous);
+/// // data of a module
+/// let data = RegionFilter::new().writable().module("libgame.so");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RegionFilter {
+    executable: bool,
+    writable: bool,
+    range: Option<Range<usize>>,
+    module: Option<String>,
+    kinds: Vec<RegionKind>,
+}
+
+impl RegionFilter {
+    /// create a filter which selects every readable region, except special mappings
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// only select executable regions
+    pub fn executable(mut self) -> Self {
+        self.executable = true;
+        self
+    }
+    /// only select writable regions
+    pub fn writable(mut self) -> Self {
+        self.writable = true;
+        self
+    }
+    /// only select memory within <range>, regions which are partly inside are clipped to it
+    pub fn range(mut self, range: Range<usize>) -> Self {
+        self.range = Some(range);
+        self
+    }
+    /// only select regions mapped from the file named <name>, e.g. `libc.so.6` or `kernel32.dll`
+    pub fn module(mut self, name: impl Into<String>) -> Self {
+        self.module = Some(name.into());
+        self
+    }
+    /// only select regions of <kind>, can be called multiple times to select multiple kinds.
+    /// special mappings are only selected when [`RegionKind::Other`] is asked for. reading `[vvar]` from within the
+    /// process can crash it.
+    pub fn kind(mut self, kind: RegionKind) -> Self {
+        self.kinds.push(kind);
+        self
+    }
+    /// check if <region> is selected by the filter, ignoring the address range
+    fn selects(&self, region: &MemoryRegion) -> bool {
+        let protections = region.get_protections();
+        protections.is_readable()
+            && (!self.executable || protections.is_executable())
+            && (!self.writable || protections.is_writable())
+            && match self.kinds.is_empty() {
+                true => region.kind != RegionKind::Other,
+                false => self.kinds.contains(&region.kind),
+            }
+            && self.module.as_deref().is_none_or(|name| {
+                region
+                    .get_path()
+                    .and_then(Path::file_name)
+                    .and_then(|x| x.to_str())
+                    .is_some_and(|x| module_name_eq(x, name))
+            })
+    }
+    /// applies the filter to <region>, clipping it to the address range
+    pub fn apply(&self, mut region: MemoryRegion) -> Option<MemoryRegion> {
+        if !self.selects(&region) {
+            return None;
+        }
+        if let Some(range) = &self.range {
+            region.start = region.start.max(range.start);
+            region.end = region.end.min(range.end);
+            if region.start >= region.end {
+                return None;
+            }
+        }
+        Some(region)
+    }
+}
+
+/// compare a file name to a module name, ignoring case on windows
+#[cfg(windows)]
+pub(crate) fn module_name_eq(file: &str, name: &str) -> bool {
+    file.eq_ignore_ascii_case(name)
+}
+#[cfg(not(windows))]
+pub(crate) fn module_name_eq(file: &str, name: &str) -> bool {
+    file == name
+}
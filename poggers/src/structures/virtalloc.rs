@@ -35,4 +35,3 @@ impl<'a, T: Mem> Drop for VirtAlloc<'a, T> {
         self.intrl_free();
     }
 }
-
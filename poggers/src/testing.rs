@@ -1,6 +1,16 @@
+#[cfg(target_os = "linux")]
+use std::{ops::Range, path::Path, sync::Arc};
+
 use tracing_subscriber::{layer::SubscriberExt, Registry};
 use tracing_tree::HierarchicalLayer;
 
+#[cfg(target_os = "linux")]
+use crate::structures::{
+    modules::Module,
+    process::{External, Process},
+    regions::RegionFilter,
+};
+
 pub(crate) fn init_tracing() {
     let layer = HierarchicalLayer::default()
         .with_writer(std::io::stdout)
@@ -12,3 +22,93 @@ pub(crate) fn init_tracing() {
     let subscriber = Registry::default().with(layer);
     tracing::subscriber::set_global_default(subscriber).unwrap();
 }
+
+/// zeroed read/write memory mapped into this process, which is opened as an external process to scan it.
+/// the memory is unmapped when the mapping is dropped.
+#[cfg(target_os = "linux")]
+pub(crate) struct Mapping {
+    start: usize,
+    size: usize,
+    process: Process<External>,
+}
+
+#[cfg(target_os = "linux")]
+impl Mapping {
+    /// map <size> bytes, a multiple of the page size
+    pub(crate) fn new(size: usize) -> Self {
+        let start = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                size,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            )
+        };
+        assert_ne!(start, libc::MAP_FAILED, "failed to map {size:#X} bytes");
+        Self {
+            start: start as usize,
+            size,
+            process: Process::find_pid(std::process::id()).unwrap(),
+        }
+    }
+    /// Get the address the mapping starts at
+    pub(crate) fn start(&self) -> usize {
+        self.start
+    }
+    /// the addresses of the mapping
+    pub(crate) fn range(&self) -> Range<usize> {
+        self.start..self.start + self.size
+    }
+    /// a filter which only selects the mapping
+    pub(crate) fn filter(&self) -> RegionFilter {
+        RegionFilter::new().range(self.range())
+    }
+    /// this process, opened as an external process
+    pub(crate) fn process(&self) -> Process<External> {
+        self.process.clone()
+    }
+    /// a module named <name> over the whole mapping
+    pub(crate) fn module(&self, name: &str) -> Module<Process<External>> {
+        Module {
+            name: Arc::from(name),
+            path: Arc::from(Path::new("")),
+            base_address: self.start,
+            end_address: self.start + self.size,
+            size: self.size,
+            handle: 0,
+            owner: Arc::new(self.process()),
+        }
+    }
+    /// copy <bytes> to <offset> into the mapping
+    pub(crate) fn write(&self, offset: usize, bytes: &[u8]) {
+        assert!(offset + bytes.len() <= self.size);
+        unsafe {
+            (self.start as *mut u8)
+                .add(offset)
+                .copy_from(bytes.as_ptr(), bytes.len())
+        };
+    }
+    /// the mapping as values of <T>
+    pub(crate) fn values<T>(&mut self) -> &mut [T] {
+        unsafe { std::slice::from_raw_parts_mut(self.start as *mut T, self.size / size_of::<T>()) }
+    }
+    /// change the protection of the <size> bytes at <offset> to <prot>, one of the `libc::PROT_*` flags
+    pub(crate) fn protect(&self, offset: usize, size: usize, prot: i32) {
+        let res = unsafe { libc::mprotect((self.start + offset) as *mut libc::c_void, size, prot) };
+        assert_eq!(res, 0, "failed to protect {size:#X} bytes at {offset:#X}");
+    }
+    /// unmap the <size> bytes at the end of the mapping, so reads run into unmapped memory
+    pub(crate) fn unmap_end(&mut self, size: usize) {
+        self.size -= size;
+        unsafe { libc::munmap((self.start + self.size) as *mut libc::c_void, size) };
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        unsafe { libc::munmap(self.start as *mut libc::c_void, self.size) };
+    }
+}
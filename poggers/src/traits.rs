@@ -1,8 +1,16 @@
+use std::mem::MaybeUninit;
+
 use thiserror::Error;
 
 use crate::{
     sigscan::SigScan,
-    structures::{addr::Address, process::ProcessError, virtalloc::VirtAlloc},
+    structures::{
+        addr::{Address, RemotePtr},
+        pod::Pod,
+        process::ProcessError,
+        regions::MemoryRegion,
+        virtalloc::VirtAlloc,
+    },
 };
 
 use super::structures::protections::Protections;
@@ -16,27 +24,61 @@ pub trait Mem {
     /// ```
     /// # Safety
     /// unsafe because it does direct calls to the OS. the address supplied could be invalid.
+    /// the bytes which are read also have to be a valid <T>, which is not the case for e.g. `bool`, enums and
+    /// references. [`Mem::read_pod`] does not have this requirement.
     unsafe fn read<T>(&self, addr: usize) -> Result<T, MemError> {
-        let mut data: T = std::mem::zeroed();
+        let mut data = MaybeUninit::<T>::uninit();
         // if Self::READ_REQUIRE_PROTECTION {
         //     let old = self.alter_protection(addr, std::mem::size_of::<T>(), Protections::ExecuteReadWrite)?;
         //     self.raw_read(addr, &mut data as *mut T as *mut u8, std::mem::size_of::<T>())?;
         //     self.alter_protection(addr, std::mem::size_of::<T>(), old)?;
         // }
         // else {
+        self.raw_read(addr, data.as_mut_ptr() as *mut u8, std::mem::size_of::<T>())?;
+        // }
+        // self.raw_read(addr, &mut data as *mut T as *mut u8, std::mem::size_of::<T>())?;
+        Ok(data.assume_init())
+    }
+    /// Read the [`Pod`] <T> from memory at address <addr>, any bytes which are read are a valid <T>
+    /// ```rs
+    /// let position: [f32; 3] = process.read_pod(0x12345678)?;
+    /// ```
+    /// # Safety
+    /// unsafe because it does direct calls to the OS. the address supplied could be invalid.
+    unsafe fn read_pod<T: Pod>(&self, addr: usize) -> Result<T, MemError> {
+        self.read(addr)
+    }
+    /// Read as many [`Pod`] <T> as fit in <data> from memory at address <addr>
+    /// ```rs
+    /// let mut health = [0f32; 64];
+    /// process.read_into(0x12345678, &mut health)?;
+    /// ```
+    /// # Safety
+    /// unsafe because it does direct calls to the OS. the address supplied could be invalid.
+    unsafe fn read_into<T: Pod>(&self, addr: usize, data: &mut [T]) -> Result<(), MemError> {
         self.raw_read(
             addr,
-            &mut data as *mut T as *mut u8,
-            std::mem::size_of::<T>(),
+            data.as_mut_ptr() as *mut u8,
+            std::mem::size_of_val(data),
+        )
+    }
+    /// Read <count> [`Pod`] <T> from memory at address <addr>, without initializing the vec first
+    /// # Safety
+    /// unsafe because it does direct calls to the OS. the address supplied could be invalid.
+    unsafe fn read_vec<T: Pod>(&self, addr: usize, count: usize) -> Result<Vec<T>, MemError> {
+        let mut data: Vec<T> = Vec::with_capacity(count);
+        let spare: &mut [MaybeUninit<T>] = &mut data.spare_capacity_mut()[..count];
+        self.raw_read(
+            addr,
+            spare.as_mut_ptr() as *mut u8,
+            std::mem::size_of_val(spare),
         )?;
-        // }
-        // self.raw_read(addr, &mut data as *mut T as *mut u8, std::mem::size_of::<T>())?;
+        data.set_len(count);
         Ok(data)
     }
     /// Read raw bytes from memory at address <addr> with size <size>
     /// # Safety
     /// unsafe because it does direct calls to the OS. the address supplied could be invalid.
-
     unsafe fn read_sized(&self, addr: usize, size: usize) -> Result<Vec<u8>, MemError> {
         let mut data: Vec<u8> = vec![0; size];
         // if Self::READ_REQUIRE_PROTECTION {
@@ -46,10 +88,37 @@ pub trait Mem {
         // }
         Ok(data)
     }
+    /// Read as many bytes as possible from memory at address <addr> into <data>
+    /// returns the amount of bytes which were readable, this will only be less than the length of <data> if the read
+    /// was cut short (e.g. the span runs into an unmapped page).
+    /// ```rs
+    /// let mut buf = [0u8; 0x2000];
+    /// let readable = process.read_partial(0x12345678, &mut buf)?;
+    /// let data = &buf[..readable];
+    /// ```
+    /// # Safety
+    /// unsafe because it does direct calls to the OS. the address supplied could be invalid.
+    unsafe fn read_partial(&self, addr: usize, data: &mut [u8]) -> Result<usize, MemError> {
+        match self.raw_read(addr, data.as_mut_ptr(), data.len()) {
+            Ok(()) => Ok(data.len()),
+            Err(MemError::ReadFailure(_, read, _)) if read > 0 => Ok(read),
+            Err(e) => Err(e),
+        }
+    }
+    /// Read every `(addr, buf)` pair of <entries>, filling each buffer with the memory at its address.
+    /// returns whether each entry could be read, in the same order as <entries>. platforms with vectored reads read
+    /// many entries per call, otherwise every entry is read on its own. see [`ReadBatch`](crate::structures::batch::ReadBatch) to queue the entries.
+    /// # Safety
+    /// unsafe because it does direct calls to the OS. the addresses supplied could be invalid.
+    unsafe fn read_batch(&self, entries: &mut [(usize, &mut [u8])]) -> Vec<Result<(), MemError>> {
+        entries
+            .iter_mut()
+            .map(|(addr, buf)| self.raw_read(*addr, buf.as_mut_ptr(), buf.len()))
+            .collect()
+    }
     /// Write <T> to memory at address <addr>
     /// # Safety
     /// unsafe because it does direct calls to the OS. the address supplied could be invalid.
-
     unsafe fn write<T>(&self, addr: usize, data: &T) -> Result<(), MemError> {
         // if Self::WRITE_REQUIRE_PROTECTION {
         //     let old = self.alter_protection(addr, std::mem::size_of::<T>(),Protections::ReadWrite)?;
@@ -65,10 +134,25 @@ pub trait Mem {
         // }
         Ok(())
     }
+    /// Write the [`Pod`] <data> to memory at address <addr>, the counterpart of [`Mem::read_pod`]
+    /// # Safety
+    /// unsafe because it does direct calls to the OS. the address supplied could be invalid.
+    unsafe fn write_pod<T: Pod>(&self, addr: usize, data: &T) -> Result<(), MemError> {
+        self.write(addr, data)
+    }
+    /// Write every [`Pod`] <T> in <data> to memory at address <addr>
+    /// # Safety
+    /// unsafe because it does direct calls to the OS. the address supplied could be invalid.
+    unsafe fn write_slice<T: Pod>(&self, addr: usize, data: &[T]) -> Result<(), MemError> {
+        self.raw_write(
+            addr,
+            data.as_ptr() as *const u8,
+            std::mem::size_of_val(data),
+        )
+    }
     /// Write raw bytes to memory at address <addr>
     /// # Safety
     /// unsafe because it does direct calls to the OS. the address supplied could be invalid.
-
     unsafe fn write_raw(&self, addr: usize, data: &[u8]) -> Result<(), MemError> {
         // if Self::WRITE_REQUIRE_PROTECTION {
         //     let old = self.alter_protection(addr, data.len(),Protections::ReadWrite)?;
@@ -83,19 +167,36 @@ pub trait Mem {
     /// Fetch a page of memory at address <addr>
     /// # Safety
     /// unsafe because it does direct calls to the OS. the address supplied could be invalid.
-
     unsafe fn fetch_page(&self, addr: usize) -> Result<[u8; 0x1000], MemError> {
         let mut data: [u8; 0x1000] = [0; 0x1000];
         self.raw_read(addr, data.as_mut_ptr(), 0x1000)?;
         Ok(data)
     }
+    /// The size of a pointer in the process in bytes, 4 for a 32 bit process running on a 64 bit os.
+    /// defaults to the size of a pointer in this process.
+    fn pointer_width(&self) -> usize {
+        std::mem::size_of::<usize>()
+    }
+    /// Read a pointer of [`Mem::pointer_width`] bytes at address <addr>
+    /// # Safety
+    /// unsafe because it does direct calls to the OS. the address supplied could be invalid.
+    unsafe fn read_pointer(&self, addr: usize) -> Result<usize, MemError> {
+        read_pointer_sized(self, addr, self.pointer_width())
+    }
     /// get a wrapper around an address
-    fn address(&self, size: usize) -> Address<Self>
+    fn address(&self, size: usize) -> Address<'_, Self>
     where
         Self: Sized + SigScan + Clone,
     {
         Address::new(self, size)
     }
+    /// get a typed pointer to a <T> at <addr>
+    fn ptr<T>(&self, addr: usize) -> RemotePtr<'_, Self, T>
+    where
+        Self: Sized,
+    {
+        RemotePtr::new(self, addr)
+    }
     /// Allocate memory to process begninning at <addr> with size <size>, needs implementation per platform
     /// This will automatically free the memory when the VirtAlloc is dropped
     /// To prevent this from happening use forget.
@@ -106,7 +207,7 @@ pub trait Mem {
         addr: Option<usize>,
         size: usize,
         prot: Protections,
-    ) -> Result<VirtAlloc<Self>, MemError>
+    ) -> Result<VirtAlloc<'_, Self>, MemError>
     where
         Self: Sized,
     {
@@ -117,6 +218,20 @@ pub trait Mem {
             proc: self,
         })
     }
+    /// Query the mapped region of memory which contains <addr>
+    /// ```rs
+    /// let region = process.query(0x12345678)?;
+    /// println!("{:X}-{:X} {}", region.get_start(), region.get_end(), region.get_protections());
+    /// ```
+    fn query(&self, addr: usize) -> Result<MemoryRegion, MemError> {
+        self.regions()?
+            .find(|region| region.contains(addr))
+            .ok_or(MemError::QueryFailure(addr))
+    }
+    /// Iterate over every mapped region of memory, ordered by address, needs implementation per platform
+    fn regions(&self) -> Result<impl Iterator<Item = MemoryRegion>, MemError> {
+        Err::<std::iter::Empty<MemoryRegion>, _>(MemError::Unsupported)
+    }
     #[cfg(windows)]
     /// Query a page of memory at address <addr>
     /// # Safety
@@ -128,7 +243,6 @@ pub trait Mem {
     /// Alter the protection of a memory region, needs implementation per platform
     /// # Safety
     /// unsafe because it does direct calls to the OS. the address supplied could be invalid.
-
     unsafe fn alter_protection(
         &self,
         addr: usize,
@@ -138,17 +252,14 @@ pub trait Mem {
     /// Read raw bytes from memory at address <addr> with size <size>, needs implementation per platform
     /// # Safety
     /// unsafe because it does direct calls to the OS. the address supplied could be invalid.
-
     unsafe fn raw_read(&self, addr: usize, data: *mut u8, size: usize) -> Result<(), MemError>;
     /// Write raw bytes to memory at address <addr> with size <size>, needs implementation per platform
     /// # Safety
     /// unsafe because it does direct calls to the OS. the address supplied could be invalid.
-
     unsafe fn raw_write(&self, addr: usize, data: *const u8, size: usize) -> Result<(), MemError>;
     /// Allocate memory to process begninning at <addr> with size <size>, needs implementation per platform
     /// # Safety
     /// unsafe because it does direct calls to the OS. the address supplied could be invalid.
-
     unsafe fn raw_virtual_alloc(
         &self,
         addr: Option<usize>,
@@ -161,15 +272,32 @@ pub trait Mem {
     unsafe fn raw_virtual_free(&self, addr: usize, size: usize) -> Result<(), MemError>;
 }
 
+/// read a pointer of <width> bytes, avoids looking up the pointer width for every level of a pointer chain
+pub(crate) unsafe fn read_pointer_sized<M: Mem + ?Sized>(
+    mem: &M,
+    addr: usize,
+    width: usize,
+) -> Result<usize, MemError> {
+    match width {
+        4 => Ok(mem.read::<u32>(addr)? as usize),
+        _ => mem.read::<usize>(addr),
+    }
+}
+
 /// Mem-trait Failures
 #[derive(Debug, Error)]
 pub enum MemError {
-    /// Read failed
-    #[error("Read failed [{0:X}]")]
-    ReadFailure(usize),
-    /// Write failed
-    #[error("Write failed [{0:X}]")]
-    WriteFailure(usize),
+    /// Read failed, with the amount of bytes which were read before failing and the os error code
+    ///
+    /// the os error code is the errno on linux, the win32 error code (as from `GetLastError`) on windows
+    /// and the `kern_return_t` on macos
+    #[error("Read failed [{0:X}] ({1:X} bytes read, os error {2})")]
+    ReadFailure(usize, usize, i32),
+    /// Write failed, with the amount of bytes which were written before failing and the os error code
+    ///
+    /// the os error code is the same per platform as for [`MemError::ReadFailure`]
+    #[error("Write failed [{0:X}] ({1:X} bytes written, os error {2})")]
+    WriteFailure(usize, usize, i32),
     /// Protection update failed
     #[error("Protection update to {1} failed [{0:X}]+{1:X}")]
     ProtectFailure(usize, usize, Protections),
@@ -179,6 +307,21 @@ pub enum MemError {
     /// Failed to free memory
     #[error("VirtualFree failed [{0:X}]+{1:X}")]
     FreeFailure(usize, usize),
+    /// The address is not within any mapped region
+    #[error("Query failed [{0:X}]")]
+    QueryFailure(usize),
+    /// Unable to enumerate the mapped regions of the process
+    #[error("Unable to enumerate memory regions")]
+    RegionEnumFailure,
+    /// The instruction at the address was not the one which was expected, with the opcode which was found
+    #[error("Unexpected instruction [{0:X}] (opcode {1:02X})")]
+    UnexpectedInstruction(usize, u8),
+    /// Reading a pointer of a pointer chain failed, with the level (starting at 0) and the address of the pointer
+    #[error("Pointer chain broke at level {0} [{1:X}]")]
+    PointerChainFailure(usize, usize, #[source] Box<MemError>),
+    /// A `Pointer<T>` was used with a process whose pointers are a different width, with the width of the process
+    #[error("Pointer<T> is {} bytes but the process uses {0} byte pointers", std::mem::size_of::<usize>())]
+    PointerWidthMismatch(usize),
     /// unsupported function for target os
     #[error("Unsupported")]
     Unsupported,
@@ -187,3 +330,31 @@ pub enum MemError {
     /// Unable to get task
     ProcessError(#[from] ProcessError),
 }
+
+impl MemError {
+    /// did a read or write fail because the memory is not mapped or not accessible, as opposed to e.g. the process
+    /// having exited. scans skip memory which faults, but stop on any other error.
+    pub fn is_fault(&self) -> bool {
+        match self {
+            Self::ReadFailure(_, _, code) | Self::WriteFailure(_, _, code) => is_fault_code(*code),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn is_fault_code(code: i32) -> bool {
+    code == libc::EFAULT || code == libc::EIO
+}
+#[cfg(windows)]
+fn is_fault_code(code: i32) -> bool {
+    use windows::Win32::Foundation::{ERROR_INVALID_ADDRESS, ERROR_NOACCESS, ERROR_PARTIAL_COPY};
+    [ERROR_PARTIAL_COPY, ERROR_NOACCESS, ERROR_INVALID_ADDRESS]
+        .iter()
+        .any(|error| error.0 == code as u32)
+}
+#[cfg(target_os = "macos")]
+fn is_fault_code(code: i32) -> bool {
+    code == mach::kern_return::KERN_INVALID_ADDRESS
+        || code == mach::kern_return::KERN_PROTECTION_FAILURE
+}
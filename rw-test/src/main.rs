@@ -1,11 +1,15 @@
-use std::time::Duration;
-static STAT_VAL: i32 = 0x7116491;
+use std::{
+    sync::atomic::{AtomicI32, Ordering},
+    time::Duration,
+};
+// atomic so that it lives in writable memory and is re-read every loop
+static STAT_VAL: AtomicI32 = AtomicI32::new(0x7116491);
 
 fn main() {
     unsafe {
-        println!("{:p}", &STAT_VAL as *const i32);
+        println!("{:p}", STAT_VAL.as_ptr());
         loop {
-            println!("{}", &STAT_VAL);
+            println!("{}", STAT_VAL.load(Ordering::Relaxed));
 
             // sig scan asm
             let mut _test = 0u32;